@@ -62,9 +62,17 @@ fn default_include_transactions() -> bool {
     false
 }
 
+// Deliberately has no `chain`/`asset_id`/`decimals` fields: the daemon always
+// picks the chain and asset from its own config and reads their decimals
+// from the chain's own metadata, so a malicious or buggy client can't lie
+// about an asset's decimals to skew the invoice's paid threshold.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CreateInvoiceParams {
     pub order_id: String,
+    // Require a JSON string for the amount: a bare JSON number would be
+    // parsed through serde_json's f64 path first and lose precision for
+    // large or many-decimal-place amounts before rust_decimal ever sees it.
+    #[serde(with = "rust_decimal::serde::str")]
     pub amount: Decimal,
     #[serde(default = "InvoiceCart::empty")]
     #[serde(skip_serializing_if = "InvoiceCart::is_empty")]
@@ -72,6 +80,22 @@ pub struct CreateInvoiceParams {
     pub redirect_url: String,
     #[serde(default = "default_include_transactions")]
     pub include_transactions: bool,
+    // Opaque merchant data (cart id, user email hash, etc.) echoed back
+    // verbatim in the invoice and its webhook/event payloads, so merchants
+    // don't need to maintain their own order-id-to-context mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    // Restrict the invoice to payments from one sender address. Transfers
+    // from any other address are recorded but don't count toward the
+    // invoice's received balance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_sender: Option<String>,
+    // Marks this as a test order. Carries no special handling on the daemon
+    // side - it's tracked, paid, and expired exactly like any other invoice -
+    // and exists purely so merchants can route it away from real fulfillment
+    // once it's echoed back in the invoice and its webhook/event payloads.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub test: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -84,6 +108,7 @@ pub struct GetInvoiceParams {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UpdateInvoiceParams {
     pub invoice_id: Uuid,
+    #[serde(with = "rust_decimal::serde::str")]
     pub amount: Decimal,
     #[serde(default = "InvoiceCart::empty")]
     #[serde(skip_serializing_if = "InvoiceCart::is_empty")]
@@ -108,6 +133,12 @@ pub enum InvoiceEventType {
     Updated,
     AdminCanceled,
     CustomerCanceled,
+    // Fires once, the first time the received balance satisfies the
+    // invoice's `amount`, slightly ahead of or alongside `Paid` (an overpaid
+    // invoice also gets `Paid`). Unlike `Paid`, it never re-fires if a chain
+    // reorg later reverts `status`
+    // and the invoice becomes paid again.
+    Seen,
     Paid,
     PartiallyPaid,
     Expired,
@@ -134,7 +165,7 @@ pub trait KalatoriEventExt: Serialize + Sized {
     fn entity_id(&self) -> Uuid;
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenericEvent<T: KalatoriEventExt> {
     pub id: Uuid,
     pub event_entity: EventEntity,
@@ -107,16 +107,60 @@ pub struct Invoice {
     pub asset_name: String,
     pub asset_id: String,
     pub chain: ChainType,
+    // TODO: merchants that want to recompute this amount themselves without
+    // risking rounding drift have asked for the exact base-unit integer
+    // alongside this `Decimal`. We can't add it yet: asset `decimals` are
+    // fetched live per chain client (`AssetInfoStore` in
+    // `daemon/src/chain_client.rs`) and never threaded through to this type
+    // or the `AppState`/webhook layers that build it, so there's currently no
+    // decimals value available at any of the ~10 call sites that construct
+    // this struct. Needs that plumbing first.
     pub amount: Decimal,
     pub payment_address: String,
     pub status: InvoiceStatus,
     pub payment_url: String,
     pub redirect_url: String,
     pub cart: InvoiceCart,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_sender: Option<String>,
+    // Marks a test order. Merchants can flag individual orders this way and
+    // echo it back to their own systems to route test payments away from
+    // real fulfillment, without needing a separate sandbox environment.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub test: bool,
     pub total_received_amount: Decimal,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub transactions: Vec<Transaction>,
+    /// `true` if `transactions` was capped and doesn't list every incoming
+    /// transaction for this invoice. Only ever set where `transactions` is
+    /// populated from a bounded source, e.g. a webhook callback body.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub transactions_truncated: bool,
     pub valid_till: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+impl Invoice {
+    /// Amount still owed to reach `amount`, or zero if the invoice is fully
+    /// paid or overpaid. Lets a frontend render "0.5 / 1.0 DOT received".
+    pub fn remaining_amount(&self) -> Decimal {
+        (self.amount - self.total_received_amount).max(Decimal::ZERO)
+    }
+
+    /// Amount received beyond `amount`, or zero if the invoice isn't
+    /// overpaid. Merchants can use this to size a refund for the excess.
+    pub fn overpaid_amount(&self) -> Decimal {
+        (self.total_received_amount - self.amount).max(Decimal::ZERO)
+    }
+
+    /// The total window the invoice was valid for, from creation to
+    /// expiry. Combined with `valid_till`, lets a frontend render a
+    /// countdown ("expires in 9:58") without having to do the subtraction
+    /// itself.
+    pub fn valid_for(&self) -> chrono::Duration {
+        self.valid_till - self.created_at
+    }
+}
@@ -177,3 +177,26 @@ pub fn add_headers_to_reqwest(
         HeaderValue::from_str(&encoded_signature).unwrap(),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_webhook_signature_matches_known_vector() {
+        // Known test vector: if this ever changes, merchants verifying webhook
+        // signatures with the documented algorithm will break.
+        let secret = b"test-secret";
+        let method = "POST";
+        let path = "/webhooks/incoming";
+        let body = br#"{"event":"invoice.paid","id":"123"}"#;
+        let timestamp = "1700000000";
+
+        let signature = compute_webhook_signature(secret, method, path, body, timestamp);
+
+        assert_eq!(
+            signature,
+            "55adb374e55ed7ff988396eae4f1f585ee532020ee127f4c57e50bd74dfa83bc"
+        );
+    }
+}
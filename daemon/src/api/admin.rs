@@ -30,11 +30,13 @@ use kalatori_client::types::ApiResultStructured;
 use crate::api::utils::ErrorWrapper;
 use crate::auth::session::AuthenticatedUser;
 use crate::auth::token::Role;
+use crate::balance_checker::BalanceCheckerError;
 use crate::dao::{
     DaoInvoiceError,
     DaoPayoutError,
     DaoSwapError,
     DaoTransactionError,
+    DaoWebhookEventError,
 };
 use crate::types::{
     KalatoriIntegrationSettings,
@@ -49,6 +51,7 @@ use crate::types::{
     PublicSwap,
     PublicTransaction,
     ShopPlatform,
+    WebhookEvent,
 };
 
 use super::ApiState;
@@ -176,6 +179,55 @@ async fn initiate_payout_handler(
     Ok(payout.into())
 }
 
+// ============================================================================
+// POST /admin/invoices/recheck
+// ============================================================================
+
+#[tracing::instrument(skip_all)]
+async fn recheck_invoice_handler(
+    State(state): State<ApiState>,
+    Extension(_user): Extension<AuthenticatedUser>,
+    AppJson(param): AppJson<InvoiceIdParam>,
+) -> ApiResult<PublicInvoice, BalanceCheckerError> {
+    let invoice_id = param.invoice_id;
+
+    let result = state
+        .recheck_invoice_balance(invoice_id)
+        .await?;
+
+    Ok(result.into())
+}
+
+// ============================================================================
+// GET /admin/webhooks/dead-letters
+// ============================================================================
+
+#[tracing::instrument(skip_all)]
+async fn list_dead_letter_webhooks_handler(
+    State(state): State<ApiState>,
+    Extension(_user): Extension<AuthenticatedUser>,
+) -> ApiResult<Vec<WebhookEvent>, DaoWebhookEventError> {
+    let result = state
+        .list_dead_letter_webhooks()
+        .await?;
+    Ok(result.into())
+}
+
+// ============================================================================
+// POST /admin/webhooks/replay-dead-letters
+// ============================================================================
+
+#[tracing::instrument(skip_all)]
+async fn replay_dead_letter_webhooks_handler(
+    State(state): State<ApiState>,
+    Extension(_user): Extension<AuthenticatedUser>,
+) -> ApiResult<Vec<WebhookEvent>, DaoWebhookEventError> {
+    let result = state
+        .replay_dead_letter_webhooks()
+        .await?;
+    Ok(result.into())
+}
+
 // ============================================================================
 // GET /admin/transactions
 // ============================================================================
@@ -357,6 +409,18 @@ pub fn routes() -> Router<ApiState> {
             "/api/payout/initiate",
             post(initiate_payout_handler),
         )
+        .route(
+            "/api/invoice/recheck",
+            post(recheck_invoice_handler),
+        )
+        .route(
+            "/api/webhooks/dead-letters",
+            get(list_dead_letter_webhooks_handler),
+        )
+        .route(
+            "/api/webhooks/replay-dead-letters",
+            post(replay_dead_letter_webhooks_handler),
+        )
         .route(
             "/api/transaction/list",
             get(list_transactions_handler),
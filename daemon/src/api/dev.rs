@@ -6,7 +6,10 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use axum::extract::State;
+use axum::extract::{
+    Query,
+    State,
+};
 use axum::response::{
     IntoResponse,
     Response,
@@ -16,13 +19,17 @@ use axum::routing::{
     post,
 };
 use chrono::Utc;
+use rust_decimal::Decimal;
 use serde::{
     Deserialize,
     Serialize,
 };
 use uuid::Uuid;
 
-use kalatori_client::types::ApiResultStructured;
+use kalatori_client::types::{
+    ApiResultStructured,
+    ChainType,
+};
 
 use crate::auth::session::COOKIE_NAME;
 use crate::auth::token::{
@@ -30,11 +37,15 @@ use crate::auth::token::{
     TokenClaims,
     sign_token,
 };
+use crate::chain::ReapError;
+use crate::dao::DaoInvoiceError;
 
 use crate::types::InvoiceWithReceivedAmount;
 
 use super::ApiState;
 use super::utils::{
+    ApiResult,
+    AppJson,
     SuccessWrapper,
     fallback_handler,
     method_not_allowed_fallback_handler,
@@ -57,6 +68,91 @@ async fn get_invoices_registry_state(
     result.into()
 }
 
+#[derive(Deserialize)]
+struct GetInvoiceByAddressQuery {
+    chain: ChainType,
+    asset_id: String,
+    address: String,
+}
+
+async fn get_invoice_by_registry_address(
+    State(state): State<ApiState>,
+    Query(query): Query<GetInvoiceByAddressQuery>,
+) -> SuccessWrapper<Option<InvoiceWithReceivedAmount>> {
+    let result = state
+        .get_invoice_by_registry_address(
+            query.chain,
+            &query.asset_id,
+            &query.address,
+        )
+        .await;
+
+    result.into()
+}
+
+#[derive(Deserialize)]
+struct GetAssetInfoByNameQuery {
+    chain: ChainType,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct AssetInfoResponse {
+    asset_id: String,
+    decimals: u8,
+    min_balance: Decimal,
+}
+
+async fn get_asset_info_by_name(
+    State(state): State<ApiState>,
+    Query(query): Query<GetAssetInfoByNameQuery>,
+) -> SuccessWrapper<Option<AssetInfoResponse>> {
+    let result = state
+        .get_asset_info_by_name(query.chain, &query.name)
+        .await
+        .map(
+            |(asset_id, decimals, min_balance)| AssetInfoResponse {
+                asset_id,
+                decimals,
+                min_balance,
+            },
+        );
+
+    result.into()
+}
+
+#[derive(Deserialize)]
+struct ForceUntrackInvoiceRequest {
+    invoice_id: Uuid,
+}
+
+async fn force_untrack_invoice(
+    State(state): State<ApiState>,
+    AppJson(body): AppJson<ForceUntrackInvoiceRequest>,
+) -> ApiResult<(), DaoInvoiceError> {
+    state
+        .force_untrack_invoice(body.invoice_id)
+        .await?;
+
+    Ok(().into())
+}
+
+#[derive(Deserialize)]
+struct ReapInvoiceRequest {
+    invoice_id: Uuid,
+}
+
+async fn reap_invoice(
+    State(state): State<ApiState>,
+    AppJson(body): AppJson<ReapInvoiceRequest>,
+) -> ApiResult<(), ReapError> {
+    state
+        .reap_invoice(body.invoice_id)
+        .await?;
+
+    Ok(().into())
+}
+
 // ============================================================================
 // POST /dev/auth/mint-token
 // ============================================================================
@@ -165,6 +261,22 @@ pub fn routes(dev_auth: Option<Arc<DevAuthState>>) -> axum::Router<ApiState> {
             "/invoices-registry",
             get(get_invoices_registry_state),
         )
+        .route(
+            "/invoices-registry/by-address",
+            get(get_invoice_by_registry_address),
+        )
+        .route(
+            "/assets/by-name",
+            get(get_asset_info_by_name),
+        )
+        .route(
+            "/invoices-registry/force-untrack",
+            post(force_untrack_invoice),
+        )
+        .route(
+            "/invoices-registry/reap",
+            post(reap_invoice),
+        )
         .fallback(fallback_handler)
         .method_not_allowed_fallback(method_not_allowed_fallback_handler);
 
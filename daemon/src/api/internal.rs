@@ -1,16 +1,22 @@
 use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
 use axum::routing::get;
 
 use crate::dao::DaoChangesError;
 use crate::types::{
+    ExpirationSweepStats,
     GetChangesParams,
+    PublicChainTip,
     PublicChangesResponse,
+    PublicRecentEvent,
 };
 
 use super::ApiState;
 use super::utils::{
     ApiResult,
     AppQuery,
+    SuccessWrapper,
     fallback_handler,
     method_not_allowed_fallback_handler,
 };
@@ -26,9 +32,46 @@ async fn get_changes(
     Ok(result.into())
 }
 
+#[tracing::instrument(skip_all)]
+async fn get_expiration_sweep_stats(
+    State(state): State<ApiState>
+) -> SuccessWrapper<ExpirationSweepStats> {
+    state.expiration_sweep_stats().into()
+}
+
+#[tracing::instrument(skip_all)]
+async fn get_recent_events(
+    State(state): State<ApiState>
+) -> SuccessWrapper<Vec<PublicRecentEvent>> {
+    state.recent_events().await.into()
+}
+
+#[tracing::instrument(skip_all)]
+async fn get_chain_tip(State(state): State<ApiState>) -> SuccessWrapper<Vec<PublicChainTip>> {
+    state.chain_tip().await.into()
+}
+
+#[tracing::instrument(skip_all)]
+async fn get_metrics(State(state): State<ApiState>) -> impl IntoResponse {
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4",
+        )],
+        state.render_metrics().await,
+    )
+}
+
 pub fn routes() -> axum::Router<ApiState> {
     axum::Router::new()
         .route("/changes", get(get_changes))
+        .route("/chain-tip", get(get_chain_tip))
+        .route(
+            "/expiration-sweep",
+            get(get_expiration_sweep_stats),
+        )
+        .route("/metrics", get(get_metrics))
+        .route("/recent-events", get(get_recent_events))
         .fallback(fallback_handler)
         .method_not_allowed_fallback(method_not_allowed_fallback_handler)
 }
@@ -17,13 +17,19 @@ use serde::Deserialize;
 use tower_http::services::ServeDir;
 use uuid::Uuid;
 
+use crate::chain::utils::{
+    AddressValidationError,
+    validate_address,
+};
 use crate::configs::ShopMetaConfig;
 use crate::dao::DaoSwapError;
 use crate::state::SwapRequestError;
 use crate::types::{
+    ChainType,
     CreateFrontEndSwapParams,
     CreateSwapParams,
     PublicSwap,
+    ServerInfo,
     SubmittedSwapParams,
     SwapSignatureParams,
 };
@@ -40,6 +46,12 @@ struct Params {
     invoice_id: Uuid,
 }
 
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+struct ValidateAddressParams {
+    chain: ChainType,
+    address: String,
+}
+
 async fn index(ExtractState(state): ExtractState<ApiState>) -> Html<String> {
     let raw_html = include_str!("../../../static/index.html");
     let shop_meta = state.get_shop_meta();
@@ -107,10 +119,22 @@ async fn invoice(
     }
 }
 
+async fn validate_address_handler(
+    Query(payload): Query<ValidateAddressParams>
+) -> ApiResult<String, AddressValidationError> {
+    let result = validate_address(payload.chain, &payload.address)?;
+
+    Ok(result.into())
+}
+
 async fn shop_meta(ExtractState(state): ExtractState<ApiState>) -> SuccessWrapper<ShopMetaConfig> {
     state.get_shop_meta().into()
 }
 
+async fn health(ExtractState(state): ExtractState<ApiState>) -> SuccessWrapper<ServerInfo> {
+    state.server_info().into()
+}
+
 async fn create_front_end_swap(
     ExtractState(state): ExtractState<ApiState>,
     AppJson(data): AppJson<CreateFrontEndSwapParams>,
@@ -171,6 +195,11 @@ pub fn routes() -> axum::Router<ApiState> {
         .route("/", axum::routing::get(index))
         .route("/invoice", axum::routing::get(invoice))
         .route("/info", axum::routing::get(shop_meta))
+        .route(
+            "/validate-address",
+            axum::routing::get(validate_address_handler),
+        )
+        .route("/health", axum::routing::get(health))
         .route(
             "/swap/register",
             axum::routing::post(create_front_end_swap),
@@ -1,3 +1,7 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
 use kalatori_client::types::ChainType;
 use rust_decimal::Decimal;
 use uuid::Uuid;
@@ -8,9 +12,11 @@ use crate::chain::{
     TransactionsRecorderError,
 };
 use crate::chain_client::{
+    AssetHubAccountId,
     AssetHubChainConfig,
     AssetHubClient,
     BlockChainClient,
+    ChainTip,
     PolygonChainConfig,
     PolygonClient,
 };
@@ -24,12 +30,114 @@ use crate::types::{
     InvoiceWithReceivedAmount,
 };
 
-#[derive(Debug)]
+/// Signed net change between two balance readings of the same account, for
+/// operator-facing reconciliation ("how much moved through this treasury
+/// address since I last checked?") where a net outflow is a legitimate,
+/// expected result rather than an error. `Decimal` itself already has no
+/// underflow panic on a negative difference - this type exists so the sign
+/// is meaningful at the call site instead of every caller re-deriving
+/// "inflow or outflow" from a bare `Decimal` by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetFlow {
+    Inflow(Decimal),
+    Outflow(Decimal),
+}
+
+impl NetFlow {
+    /// `after - before`, classified by sign. Both magnitudes are
+    /// non-negative; the variant carries the direction.
+    fn between(
+        before: Decimal,
+        after: Decimal,
+    ) -> Self {
+        let delta = after - before;
+
+        if delta.is_sign_negative() {
+            Self::Outflow(-delta)
+        } else {
+            Self::Inflow(delta)
+        }
+    }
+
+    /// Signed magnitude: positive for an inflow, negative for an outflow.
+    pub fn as_signed_decimal(self) -> Decimal {
+        match self {
+            Self::Inflow(amount) => amount,
+            Self::Outflow(amount) => -amount,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
 pub enum BalanceCheckerError {
+    #[error("Invoice is not tracked")]
     InvoiceNotFound { invoice_id: Uuid },
+    #[error("Failed to fetch account balance from the chain")]
     FetchBalanceFailed,
+    #[error("Failed to fetch incoming transfers from the indexer")]
     FetchTransfersFailed,
+    #[error("Database error")]
     DatabaseError,
+    #[error("Overflow while summing incoming transaction amounts")]
+    AmountOverflow,
+}
+
+impl crate::api::ApiErrorExt for BalanceCheckerError {
+    fn category(&self) -> &str {
+        match self {
+            BalanceCheckerError::InvoiceNotFound {
+                ..
+            } => "ENTITY_NOT_FOUND",
+            BalanceCheckerError::FetchBalanceFailed | BalanceCheckerError::FetchTransfersFailed => {
+                "CHAIN_CLIENT_ERROR"
+            },
+            BalanceCheckerError::DatabaseError | BalanceCheckerError::AmountOverflow => {
+                "INTERNAL_ERROR"
+            },
+        }
+    }
+
+    fn code(&self) -> &str {
+        match self {
+            BalanceCheckerError::InvoiceNotFound {
+                ..
+            } => "INVOICE_NOT_TRACKED",
+            BalanceCheckerError::FetchBalanceFailed => "BALANCE_FETCH_FAILED",
+            BalanceCheckerError::FetchTransfersFailed => "TRANSFERS_FETCH_FAILED",
+            BalanceCheckerError::DatabaseError => "DATABASE_ERROR",
+            BalanceCheckerError::AmountOverflow => "AMOUNT_OVERFLOW",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            BalanceCheckerError::InvoiceNotFound {
+                ..
+            } => "The invoice is not tracked and has no record in the database.",
+            BalanceCheckerError::FetchBalanceFailed => {
+                "Failed to fetch the invoice's payment address balance from the chain."
+            },
+            BalanceCheckerError::FetchTransfersFailed => {
+                "Failed to fetch the invoice's incoming transfers from the indexer."
+            },
+            BalanceCheckerError::DatabaseError => "An internal database error occurred.",
+            BalanceCheckerError::AmountOverflow => "An internal error occurred.",
+        }
+    }
+
+    fn http_status_code(&self) -> axum::http::StatusCode {
+        match self {
+            BalanceCheckerError::InvoiceNotFound {
+                ..
+            } => axum::http::StatusCode::NOT_FOUND,
+            BalanceCheckerError::FetchBalanceFailed | BalanceCheckerError::FetchTransfersFailed => {
+                axum::http::StatusCode::BAD_GATEWAY
+            },
+            BalanceCheckerError::DatabaseError | BalanceCheckerError::AmountOverflow => {
+                axum::http::StatusCode::INTERNAL_SERVER_ERROR
+            },
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -70,8 +178,182 @@ impl<
         }
     }
 
+    /// Fetch an arbitrary account's balance in each of `asset_ids`, without
+    /// going through an invoice. Useful for checking whether an address has
+    /// been paid in any one of several accepted currencies: a merchant
+    /// accepting both DOT and USDC on the same `address` can call this with
+    /// both asset IDs and look for the first balance that meets the expected
+    /// amount, rather than issuing one `get_account_balance` call per
+    /// currency by hand.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_account_balances(
+        &self,
+        chain: ChainType,
+        asset_ids: &[String],
+        address: &str,
+    ) -> Result<Vec<(String, Decimal)>, BalanceCheckerError> {
+        let mut balances = Vec::with_capacity(asset_ids.len());
+
+        for asset_id in asset_ids {
+            let balance = self
+                .get_account_balance(chain, asset_id, address)
+                .await?;
+            balances.push((asset_id.clone(), balance));
+        }
+
+        Ok(balances)
+    }
+
+    /// Fetch one Asset Hub asset's balance for each of `addresses` in a
+    /// single batched RPC round trip, for reconciliation tooling that needs
+    /// to recheck many treasury/watched addresses at once rather than
+    /// issuing one [`Self::get_account_balance`] call per address. Polygon
+    /// has no equivalent batch storage API, so this is Asset Hub only.
+    #[tracing::instrument(skip(self, addresses))]
+    pub async fn get_asset_hub_account_balances(
+        &self,
+        asset_id: &str,
+        addresses: &[String],
+    ) -> Result<Vec<(String, Decimal)>, BalanceCheckerError> {
+        // We don't expect parsing errors here, unwraps should be safe
+        let asset_id: u32 = asset_id.parse().unwrap();
+        let accounts: Vec<AssetHubAccountId> = addresses
+            .iter()
+            .map(|address| address.parse().unwrap())
+            .collect();
+
+        let balances = self
+            .asset_hub_client
+            .balances_at_accounts(asset_id, &accounts)
+            .await
+            .map_err(|e| {
+                tracing::warn!(
+                    error.source = ?e,
+                    "Failed to batch-fetch account balances in order to compare with received amount"
+                );
+
+                BalanceCheckerError::FetchBalanceFailed
+            })?;
+
+        Ok(addresses
+            .iter()
+            .cloned()
+            .zip(balances)
+            .collect())
+    }
+
+    /// Net change in `address`'s `asset_id` balance since a previously
+    /// recorded reading, as a signed [`NetFlow`]. There's no historical
+    /// balance lookup on either chain client, so `since_balance` is whatever
+    /// the caller last observed (e.g. from its own reconciliation records);
+    /// this just fetches the current balance and classifies the difference.
+    /// This is unrelated to invoice overpayment handling
+    /// (`Invoice::overpaid_amount`/`remaining_amount`), which only ever
+    /// tracks a single invoice's own expected amount.
+    #[tracing::instrument(skip(self))]
+    pub async fn net_flow_since(
+        &self,
+        chain: ChainType,
+        asset_id: &str,
+        address: &str,
+        since_balance: Decimal,
+    ) -> Result<NetFlow, BalanceCheckerError> {
+        let current_balance = self
+            .get_account_balance(chain, asset_id, address)
+            .await?;
+
+        Ok(NetFlow::between(
+            since_balance,
+            current_balance,
+        ))
+    }
+
+    /// The last block the watcher has actually ingested for `chain`, for
+    /// clients and dashboards wanting to measure invoice evaluation latency.
+    /// `None` until the chain client has processed its first block.
+    pub async fn chain_tip(
+        &self,
+        chain: ChainType,
+    ) -> Option<ChainTip> {
+        match chain {
+            ChainType::PolkadotAssetHub => self.asset_hub_client.chain_tip().await,
+            ChainType::Polygon => self.polygon_client.chain_tip().await,
+        }
+    }
+
+    /// EMA of `chain`'s inter-block duration, for translating a confirmation
+    /// depth into an estimated wall-clock time. `None` until the watcher has
+    /// processed at least two blocks for that chain.
+    pub async fn block_time_estimate_millis(
+        &self,
+        chain: ChainType,
+    ) -> Option<u64> {
+        match chain {
+            ChainType::PolkadotAssetHub => {
+                self.asset_hub_client
+                    .block_time_estimate_millis()
+                    .await
+            },
+            ChainType::Polygon => {
+                self.polygon_client
+                    .block_time_estimate_millis()
+                    .await
+            },
+        }
+    }
+
+    /// The runtime `spec_version` the chain's watcher negotiated at
+    /// connection time, for operators confirming it's on the chain version
+    /// its baked-in metadata was generated from. `None` for Polygon, which
+    /// has no comparable runtime-version concept.
+    pub fn spec_version(
+        &self,
+        chain: ChainType,
+    ) -> Option<u32> {
+        match chain {
+            ChainType::PolkadotAssetHub => Some(self.asset_hub_client.spec_version()),
+            ChainType::Polygon => None,
+        }
+    }
+
+    /// Look up an asset by its human-readable symbol (e.g. `"USDC"`) among
+    /// the assets fetched from that chain at startup, for debugging tooling
+    /// that only has a symbol on hand. Always reflects the chain's own
+    /// metadata, never a caller-supplied value. Returns `(asset_id,
+    /// decimals, min_balance)`.
+    pub async fn get_asset_info_by_name(
+        &self,
+        chain: ChainType,
+        name: &str,
+    ) -> Option<(String, u8, Decimal)> {
+        match chain {
+            ChainType::PolkadotAssetHub => {
+                self.asset_hub_client
+                    .asset_info_store()
+                    .get_asset_info_by_name(name)
+                    .await
+            },
+            ChainType::Polygon => {
+                self.polygon_client
+                    .asset_info_store()
+                    .get_asset_info_by_name(name)
+                    .await
+            },
+        }
+        .map(|info| {
+            (
+                info.id.to_string(),
+                info.decimals,
+                info.min_balance,
+            )
+        })
+    }
+
+    /// Fetch an arbitrary account's balance directly from the chain, without
+    /// going through an invoice. Useful for reconciliation tooling, e.g.
+    /// checking the merchant's own treasury balance.
     #[tracing::instrument(skip(self))]
-    async fn get_account_balance(
+    pub async fn get_account_balance(
         &self,
         chain: ChainType,
         asset_id: &str,
@@ -113,6 +395,7 @@ impl<
         asset_id: &str,
         address: &str,
         invoice_id: Uuid,
+        since: DateTime<Utc>,
     ) -> Result<Vec<IncomingTransaction>, BalanceCheckerError> {
         match chain {
             ChainType::PolkadotAssetHub => {
@@ -121,7 +404,9 @@ impl<
             },
             ChainType::Polygon => self
                 .etherscan_client
-                .get_account_incoming_transfers(chain, asset_id, address, invoice_id)
+                .get_account_incoming_transfers(
+                    chain, asset_id, address, invoice_id, since,
+                )
                 .await
                 .map_err(|e| {
                     tracing::warn!(
@@ -141,6 +426,12 @@ impl<
             received_amount = %invoice.total_received_amount,
         )
     )]
+    // Live crediting (`TransfersTracker`) only ever sees transfers from the
+    // moment it's subscribed onward, so it never needs a lower bound. This
+    // reconciliation path is the one case that backfills an address's full
+    // transfer history from an indexer, which is why it's also the one place
+    // that needs `invoice.created_at` as a floor — see
+    // `EtherscanClient::get_account_incoming_transfers`.
     async fn get_and_store_transactions(
         &self,
         invoice: &mut InvoiceWithReceivedAmount,
@@ -159,7 +450,8 @@ impl<
                 chain,
                 asset_id,
                 address,
-                invoice_id
+                invoice_id,
+                invoice.invoice.created_at,
             )
             .await
             .map_err(|e| {
@@ -171,10 +463,19 @@ impl<
                 BalanceCheckerError::FetchTransfersFailed
             })?;
 
-        let total_amount: Decimal = incoming_transactions
+        let total_amount = incoming_transactions
             .iter()
-            .map(|trans| trans.transfer_info.amount)
-            .sum();
+            .try_fold(Decimal::ZERO, |acc, trans| {
+                acc.checked_add(trans.transfer_info.amount)
+            })
+            .ok_or_else(|| {
+                tracing::error!(
+                    invoice_id = %invoice_id,
+                    "Overflow while summing incoming transaction amounts"
+                );
+
+                BalanceCheckerError::AmountOverflow
+            })?;
 
         if total_amount != balance {
             // TODO: build event and send it as a webhook. It'll be a way to
@@ -274,3 +575,52 @@ impl<
         Ok(invoice)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn net_flow_between_detects_inflow() {
+        let flow = NetFlow::between(
+            Decimal::new(100, 0),
+            Decimal::new(150, 0),
+        );
+
+        assert_eq!(
+            flow,
+            NetFlow::Inflow(Decimal::new(50, 0))
+        );
+        assert_eq!(
+            flow.as_signed_decimal(),
+            Decimal::new(50, 0)
+        );
+    }
+
+    #[test]
+    fn net_flow_between_detects_outflow() {
+        let flow = NetFlow::between(
+            Decimal::new(150, 0),
+            Decimal::new(100, 0),
+        );
+
+        assert_eq!(
+            flow,
+            NetFlow::Outflow(Decimal::new(50, 0))
+        );
+        assert_eq!(
+            flow.as_signed_decimal(),
+            Decimal::new(-50, 0)
+        );
+    }
+
+    #[test]
+    fn net_flow_between_is_inflow_when_unchanged() {
+        let flow = NetFlow::between(
+            Decimal::new(100, 0),
+            Decimal::new(100, 0),
+        );
+
+        assert_eq!(flow, NetFlow::Inflow(Decimal::ZERO));
+    }
+}
@@ -5,7 +5,10 @@ mod transfer_tracker;
 pub mod utils;
 
 pub use executor::TransfersExecutor;
-pub use invoice_registry::InvoiceRegistry;
+pub use invoice_registry::{
+    InvoiceRegistry,
+    ReapError,
+};
 #[cfg_attr(test, mockall_double::double)]
 pub use transactions_recorder::TransactionsRecorder;
 pub use transactions_recorder::TransactionsRecorderError;
@@ -187,11 +187,12 @@ type BoxedTransferFuture = std::pin::Pin<Box<dyn Future<Output = TransactionExec
 async fn send_transfer_request<T: ChainConfig, C: BlockChainClient<T>>(
     client: Arc<C>,
     signed_transaction: SignedTransaction<T>,
+    sender: T::AccountId,
     request: OutgoingTransferRequest,
     transaction: Transaction,
 ) -> TransactionExecutionData {
     let response = client
-        .submit_and_watch_transaction(signed_transaction)
+        .submit_and_watch_transaction(signed_transaction, sender)
         .await;
 
     let mut meta = request.retry_meta;
@@ -357,7 +358,7 @@ impl<
         &self,
         client: &Arc<C>,
         request: &OutgoingTransferRequest,
-    ) -> Result<SignedTransaction<T>, ChainExecutorError> {
+    ) -> Result<(SignedTransaction<T>, T::AccountId), ChainExecutorError> {
         let sender = request
             .source_address
             .parse()
@@ -365,6 +366,16 @@ impl<
                 reason: "Invalid source address".to_string(),
             })?;
 
+        // `T::AccountId` isn't `Clone`, so the sender is parsed a second time
+        // below to hand back to the caller alongside the signed transaction
+        // (needed for nonce resync on submission).
+        let sender_for_submission = request
+            .source_address
+            .parse()
+            .map_err(|_| ChainExecutorError::BuildTransfer {
+                reason: "Invalid source address".to_string(),
+            })?;
+
         let recipient = request
             .destination_params
             .destination_address
@@ -423,7 +434,10 @@ impl<
                 }
             })?;
 
-        Ok(signed_transaction)
+        Ok((
+            signed_transaction,
+            sender_for_submission,
+        ))
     }
 
     #[instrument(skip_all)]
@@ -479,7 +493,7 @@ impl<
         request: OutgoingTransferRequest,
         transaction_id: Uuid,
     ) -> Result<BoxedTransferFuture, ChainExecutorError> {
-        let signed_transaction = self
+        let (signed_transaction, sender) = self
             .build_and_sign_transfer(&client, &request)
             .await?;
 
@@ -499,6 +513,7 @@ impl<
         let fut = Box::pin(send_transfer_request(
             client,
             signed_transaction,
+            sender,
             request,
             transaction,
         ));
@@ -935,12 +950,23 @@ impl<
                 }
             })?;
 
-        tracing::warn!(
-            transaction_id = %transaction_id,
-            invoice_id = %invoice_id,
-            is_retriable = error.is_retriable,
-            "Transfer execution failed",
-        );
+        if error.is_retriable {
+            tracing::warn!(
+                transaction_id = %transaction_id,
+                invoice_id = %invoice_id,
+                "Transfer execution failed, will be retried",
+            );
+        } else {
+            // Non-retriable: the payout/refund is now stuck in a terminal
+            // Failed state and needs manual intervention (e.g. re-funding the
+            // source address's gas reserve) before it can be retried.
+            tracing::error!(
+                transaction_id = %transaction_id,
+                invoice_id = %invoice_id,
+                failure_message = error.retry_meta.failure_message.as_deref().unwrap_or_default(),
+                "Transfer execution failed permanently, funds are stuck on the source address",
+            );
+        }
 
         Ok(())
     }
@@ -1210,17 +1236,18 @@ mod tests {
                     })
                 });
 
-            let result = executor
+            let (signed_transaction, sender) = executor
                 .build_and_sign_transfer(&Arc::new(polygon_client), &request)
                 .await
                 .unwrap();
 
             assert_eq!(
-                result,
+                signed_transaction,
                 SignedTransaction {
                     transaction: default_polygon_signed_transaction()
                 }
             );
+            assert_eq!(sender, source_address);
         }
 
         // Test case 2:
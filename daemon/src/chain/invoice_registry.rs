@@ -1,31 +1,233 @@
 use std::collections::{
     HashMap,
     HashSet,
+    VecDeque,
 };
 use std::sync::Arc;
+use std::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
 
+use chrono::{
+    DateTime,
+    Utc,
+};
 use rust_decimal::Decimal;
-use tokio::sync::RwLock;
+use tokio::sync::{
+    RwLock,
+    broadcast,
+};
 use uuid::Uuid;
 
 use crate::types::{
     ChainType,
+    GenericEvent,
     InvoiceStatus,
     InvoiceWithReceivedAmount,
+    PublicInvoice,
+    PublicRecentEvent,
 };
 
+/// Capacity of the in-process invoice status event channel. Generous enough
+/// that a momentarily slow subscriber doesn't drop events under normal
+/// traffic; slower subscribers just lag and skip ahead rather than blocking
+/// the tracker.
+const INVOICE_EVENTS_CHANNEL_CAPACITY: usize = 256;
+
+/// Why a [`InvoiceRegistry::reap_invoice`] call didn't remove the invoice.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ReapError {
+    /// The invoice isn't being tracked, so there's nothing to reap.
+    #[error("Invoice is not being tracked")]
+    NotFound,
+    /// The invoice is still active (`Waiting`/`PartiallyPaid`), so it still
+    /// has pending expected funds and reaping would lose track of them. Use
+    /// [`InvoiceRegistry::remove_invoice`] to remove it unconditionally.
+    #[error("Invoice still has pending expected funds")]
+    StillActive,
+}
+
+impl crate::api::ApiErrorExt for ReapError {
+    fn category(&self) -> &str {
+        match self {
+            ReapError::NotFound => "ENTITY_NOT_FOUND",
+            ReapError::StillActive => "STATUS_CONSTRAINT_VIOLATION",
+        }
+    }
+
+    fn code(&self) -> &str {
+        match self {
+            ReapError::NotFound => "INVOICE_NOT_TRACKED",
+            ReapError::StillActive => "INVOICE_STILL_ACTIVE",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            ReapError::NotFound => "The invoice is not being tracked.",
+            ReapError::StillActive => {
+                "The invoice still has pending expected funds and can't be reaped."
+            },
+        }
+    }
+
+    fn http_status_code(&self) -> axum::http::StatusCode {
+        match self {
+            ReapError::NotFound => axum::http::StatusCode::NOT_FOUND,
+            ReapError::StillActive => axum::http::StatusCode::CONFLICT,
+        }
+    }
+}
+
+/// Lifetime invoice-lifecycle counters for Prometheus scraping, exposed via
+/// `GET /internal/metrics` (see `daemon/src/api/internal.rs`). Lives on
+/// [`InvoiceRegistry`] rather than threaded separately through every
+/// component, since every component that causes one of these transitions
+/// (`AppState`, `TransactionsRecorder`, `ExpirationDetector`) already holds
+/// a clone of the registry.
+#[derive(Debug, Clone, Default)]
+struct InvoiceMetrics {
+    created: Arc<AtomicU64>,
+    paid: Arc<AtomicU64>,
+    expired: Arc<AtomicU64>,
+    reaped: Arc<AtomicU64>,
+}
+
+impl InvoiceMetrics {
+    fn render_prometheus(
+        &self,
+        active: u64,
+    ) -> String {
+        format!(
+            "# TYPE invoices_created_total counter\n\
+             invoices_created_total {}\n\
+             # TYPE invoices_paid_total counter\n\
+             invoices_paid_total {}\n\
+             # TYPE invoices_expired_total counter\n\
+             invoices_expired_total {}\n\
+             # TYPE invoices_reaped_total counter\n\
+             invoices_reaped_total {}\n\
+             # TYPE invoices_active gauge\n\
+             invoices_active {}\n",
+            self.created.load(Ordering::Relaxed),
+            self.paid.load(Ordering::Relaxed),
+            self.expired.load(Ordering::Relaxed),
+            self.reaped.load(Ordering::Relaxed),
+            active
+        )
+    }
+}
+
+/// An invoice lifecycle transition recorded for post-mortem debugging, as
+/// kept by [`RecentEventsLog`].
+#[derive(Debug, Clone)]
+pub struct RecentInvoiceEvent {
+    pub timestamp: DateTime<Utc>,
+    pub kind: &'static str,
+    pub invoice_id: Uuid,
+}
+
+impl From<RecentInvoiceEvent> for PublicRecentEvent {
+    fn from(event: RecentInvoiceEvent) -> Self {
+        PublicRecentEvent {
+            timestamp: event.timestamp,
+            kind: event.kind,
+            invoice_id: event.invoice_id,
+        }
+    }
+}
+
+/// Bounded in-memory log of the most recent invoice lifecycle events
+/// (created/paid/expired/reaped), so operators can answer "what just
+/// happened" without full debug logging always on. Oldest entries are
+/// dropped once `capacity` is reached.
+#[derive(Debug, Clone)]
+struct RecentEventsLog {
+    events: Arc<RwLock<VecDeque<RecentInvoiceEvent>>>,
+    capacity: usize,
+}
+
+impl RecentEventsLog {
+    fn new(capacity: usize) -> Self {
+        RecentEventsLog {
+            events: Arc::new(RwLock::new(VecDeque::with_capacity(
+                capacity,
+            ))),
+            capacity,
+        }
+    }
+
+    async fn record(
+        &self,
+        kind: &'static str,
+        invoice_id: Uuid,
+    ) {
+        let mut events = self.events.write().await;
+
+        if events.len() >= self.capacity {
+            events.pop_front();
+        }
+
+        events.push_back(RecentInvoiceEvent {
+            timestamp: Utc::now(),
+            kind,
+            invoice_id,
+        });
+    }
+
+    async fn recent(&self) -> Vec<RecentInvoiceEvent> {
+        self.events
+            .read()
+            .await
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
 #[derive(Clone)]
 pub struct InvoiceRegistry {
+    // Keyed by `Uuid` (the invoice's real id), not a derived `PartialEq`/`Hash`
+    // on `Invoice` itself — dedup and lookup by id already fall out of this
+    // map key, so `Invoice`'s full-field `PartialEq`/`Eq` (used for things
+    // like test assertions) never needs to double as an identity check.
     invoices: Arc<RwLock<HashMap<Uuid, InvoiceWithReceivedAmount>>>,
+    events: broadcast::Sender<GenericEvent<PublicInvoice>>,
+    metrics: InvoiceMetrics,
+    recent_events: RecentEventsLog,
 }
 
 impl InvoiceRegistry {
-    pub fn new() -> Self {
+    pub fn new(recent_events_capacity: usize) -> Self {
+        let (events, _) = broadcast::channel(INVOICE_EVENTS_CHANNEL_CAPACITY);
+
         InvoiceRegistry {
             invoices: Arc::new(RwLock::new(HashMap::new())),
+            events,
+            metrics: InvoiceMetrics::default(),
+            recent_events: RecentEventsLog::new(recent_events_capacity),
         }
     }
 
+    /// Subscribe to invoice status change events as they're emitted, for
+    /// in-process consumers (e.g. driving a websocket) that want to avoid an
+    /// HTTP round-trip through the webhook delivery pipeline. A slow
+    /// subscriber lags and skips ahead rather than blocking publishers.
+    pub fn subscribe(&self) -> broadcast::Receiver<GenericEvent<PublicInvoice>> {
+        self.events.subscribe()
+    }
+
+    /// Publish an invoice status change event to subscribers. No-op if
+    /// nobody is currently subscribed.
+    pub fn publish_event(
+        &self,
+        event: GenericEvent<PublicInvoice>,
+    ) {
+        // Ignore the error: it only means there are no subscribers right now.
+        let _ = self.events.send(event);
+    }
+
     pub async fn add_invoice(
         &self,
         record: InvoiceWithReceivedAmount,
@@ -45,6 +247,9 @@ impl InvoiceRegistry {
         }
     }
 
+    /// Remove an invoice unconditionally, regardless of whether it's still
+    /// active. Use [`InvoiceRegistry::reap_invoice`] instead when the
+    /// invoice should only be removed once it's settled.
     pub async fn remove_invoice(
         &self,
         invoice_id: &Uuid,
@@ -53,6 +258,91 @@ impl InvoiceRegistry {
         invoices.remove(invoice_id)
     }
 
+    /// Remove an invoice only if it's no longer active, refusing with
+    /// [`ReapError::StillActive`] if it still has pending expected funds.
+    pub async fn reap_invoice(
+        &self,
+        invoice_id: &Uuid,
+    ) -> Result<InvoiceWithReceivedAmount, ReapError> {
+        let mut invoices = self.invoices.write().await;
+
+        match invoices.get(invoice_id) {
+            None => Err(ReapError::NotFound),
+            Some(invoice) if invoice.invoice.status.is_active() => Err(ReapError::StillActive),
+            Some(_) => {
+                let invoice = invoices
+                    .remove(invoice_id)
+                    .expect("just checked this invoice is present");
+                self.metrics
+                    .reaped
+                    .fetch_add(1, Ordering::Relaxed);
+                self.recent_events
+                    .record("reaped", *invoice_id)
+                    .await;
+                Ok(invoice)
+            },
+        }
+    }
+
+    /// Record that a new invoice has been created, for the
+    /// `invoices_created_total` metric. Called from
+    /// `AppState::create_invoice` rather than from [`Self::add_invoice`],
+    /// since not every caller of `add_invoice` (e.g. reloading active
+    /// invoices on startup) represents a newly created one.
+    pub async fn record_invoice_created(
+        &self,
+        invoice_id: Uuid,
+    ) {
+        self.metrics
+            .created
+            .fetch_add(1, Ordering::Relaxed);
+        self.recent_events
+            .record("created", invoice_id)
+            .await;
+    }
+
+    /// Record that an invoice reached `Paid`/`OverPaid`, for the
+    /// `invoices_paid_total` metric.
+    pub async fn record_invoice_paid(
+        &self,
+        invoice_id: Uuid,
+    ) {
+        self.metrics
+            .paid
+            .fetch_add(1, Ordering::Relaxed);
+        self.recent_events
+            .record("paid", invoice_id)
+            .await;
+    }
+
+    /// Record that an invoice reached `UnpaidExpired`/`PartiallyPaidExpired`,
+    /// for the `invoices_expired_total` metric.
+    pub async fn record_invoice_expired(
+        &self,
+        invoice_id: Uuid,
+    ) {
+        self.metrics
+            .expired
+            .fetch_add(1, Ordering::Relaxed);
+        self.recent_events
+            .record("expired", invoice_id)
+            .await;
+    }
+
+    /// Render all invoice lifecycle counters plus the current `invoices_active`
+    /// gauge as Prometheus text exposition format.
+    pub async fn render_metrics(&self) -> String {
+        let active = self.invoices.read().await.len() as u64;
+        self.metrics.render_prometheus(active)
+    }
+
+    /// The most recent invoice lifecycle events, oldest first, for post-mortem
+    /// debugging via `GET /internal/recent-events`. Bounded by
+    /// `PaymentsConfig::recent_events_buffer_size`.
+    pub async fn recent_events(&self) -> Vec<RecentInvoiceEvent> {
+        self.recent_events.recent().await
+    }
+
     #[cfg_attr(not(test), expect(dead_code))]
     pub async fn remove_invoices(
         &self,
@@ -132,6 +422,18 @@ impl InvoiceRegistry {
         invoices.len()
     }
 
+    /// Ids of every invoice currently tracked. Used for startup reconciliation
+    /// passes that need to revisit each one (see `main.rs`'s balance
+    /// backfill), not for anything driven by live chain events.
+    pub async fn invoice_ids(&self) -> Vec<Uuid> {
+        self.invoices
+            .read()
+            .await
+            .keys()
+            .copied()
+            .collect()
+    }
+
     #[cfg(feature = "dev_api")]
     pub async fn state(&self) -> HashMap<Uuid, InvoiceWithReceivedAmount> {
         self.invoices.read().await.clone()
@@ -143,13 +445,14 @@ mod tests {
     use crate::types::{
         Invoice,
         default_invoice,
+        default_webhook_event,
     };
 
     use super::*;
 
     #[tokio::test]
     async fn test_invoice_registry() {
-        let registry = InvoiceRegistry::new();
+        let registry = InvoiceRegistry::new(256);
 
         // Registry should be empty by default
         assert_eq!(registry.invoices_count().await, 0);
@@ -504,4 +807,115 @@ mod tests {
                 .is_some()
         );
     }
+
+    #[tokio::test]
+    async fn test_reap_invoice() {
+        let registry = InvoiceRegistry::new(256);
+
+        // Reaping an untracked invoice fails with NotFound
+        assert_eq!(
+            registry
+                .reap_invoice(&Uuid::new_v4())
+                .await
+                .unwrap_err(),
+            ReapError::NotFound
+        );
+
+        // An active invoice is refused
+        let waiting_invoice = Invoice {
+            status: InvoiceStatus::Waiting,
+            ..default_invoice()
+        }
+        .with_amount(Decimal::ZERO);
+        let waiting_invoice_id = waiting_invoice.invoice.id;
+
+        registry
+            .add_invoice(waiting_invoice)
+            .await;
+
+        assert_eq!(
+            registry
+                .reap_invoice(&waiting_invoice_id)
+                .await
+                .unwrap_err(),
+            ReapError::StillActive
+        );
+        assert!(
+            registry
+                .get_invoice(&waiting_invoice_id)
+                .await
+                .is_some()
+        );
+
+        // A settled invoice is removed and returned
+        let paid_invoice = Invoice {
+            status: InvoiceStatus::Paid,
+            ..default_invoice()
+        }
+        .with_amount(Decimal::ONE_HUNDRED);
+        let paid_invoice_id = paid_invoice.invoice.id;
+
+        registry
+            .add_invoice(paid_invoice.clone())
+            .await;
+
+        assert_eq!(
+            registry
+                .reap_invoice(&paid_invoice_id)
+                .await,
+            Ok(paid_invoice)
+        );
+        assert!(
+            registry
+                .get_invoice(&paid_invoice_id)
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recent_events_is_bounded_and_ordered() {
+        let registry = InvoiceRegistry::new(2);
+        let invoice_ids: Vec<_> = (0..3).map(|_| Uuid::new_v4()).collect();
+
+        registry
+            .record_invoice_created(invoice_ids[0])
+            .await;
+        registry
+            .record_invoice_paid(invoice_ids[1])
+            .await;
+        registry
+            .record_invoice_expired(invoice_ids[2])
+            .await;
+
+        let recent = registry.recent_events().await;
+
+        // Capacity is 2, so the oldest entry was dropped.
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].kind, "paid");
+        assert_eq!(recent[0].invoice_id, invoice_ids[1]);
+        assert_eq!(recent[1].kind, "expired");
+        assert_eq!(recent[1].invoice_id, invoice_ids[2]);
+    }
+
+    #[tokio::test]
+    async fn test_invoice_events() {
+        let registry = InvoiceRegistry::new(256);
+        let mut subscriber = registry.subscribe();
+
+        let event = default_webhook_event(Uuid::new_v4());
+        registry.publish_event(event.clone());
+
+        let received = subscriber.recv().await.unwrap();
+        assert_eq!(received.id, event.id);
+        assert_eq!(received.payload.id, event.payload.id);
+    }
+
+    #[tokio::test]
+    async fn test_invoice_events_no_subscribers_is_a_no_op() {
+        let registry = InvoiceRegistry::new(256);
+
+        // Publishing with nobody subscribed must not panic or block.
+        registry.publish_event(default_webhook_event(Uuid::new_v4()));
+    }
 }
@@ -19,6 +19,7 @@ use crate::types::{
     PublicInvoice,
     Refund,
     SwapChainType,
+    TransactionType,
     TransferDestinationParams,
 };
 
@@ -61,9 +62,10 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
         public_invoice: PublicInvoice,
         event_type: InvoiceEventType,
     ) -> Result<(), TransactionsRecorderError> {
-        let event = public_invoice
-            .build_event(event_type)
-            .into();
+        let public_event = public_invoice.build_event(event_type);
+        self.registry
+            .publish_event(public_event.clone());
+        let event = public_event.into();
 
         dao_transaction
             .create_webhook_event(event)
@@ -96,6 +98,16 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
             destination_asset_id: invoice.asset_id.clone(),
         };
 
+        // Withhold a per-chain buffer so the sweep doesn't leave the payment
+        // address short on the existential deposit or its own payout fees.
+        let sweep_fee_buffer = self
+            .config
+            .sweep_fee_buffer
+            .get(&chain)
+            .copied()
+            .unwrap_or_default();
+        let amount = (amount - sweep_fee_buffer).max(Decimal::ZERO);
+
         let payout = Payout::from_invoice(invoice, destination_params, amount);
 
         dao_transaction
@@ -109,6 +121,7 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
     async fn store_transaction(
         &self,
         transaction: IncomingTransaction,
+        previous_status: InvoiceStatus,
         invoice_status: InvoiceStatus,
         total_received_amount: Decimal,
     ) -> Result<(), TransactionsRecorderError> {
@@ -139,12 +152,56 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
             .await
             .map_err(|_e| TransactionsRecorderError::DaoTransactionError)?;
 
-        let public_invoice = invoice
+        let mut public_invoice = invoice
             .clone()
             .with_amount(total_received_amount)
             .into_public_invoice(&self.config.payment_url_base);
 
+        // TODO: filter it on database query level
+        let mut incoming_transactions: Vec<_> = dao_transaction
+            .get_invoice_transactions(invoice_id)
+            .await
+            .map_err(|_e| TransactionsRecorderError::DaoTransactionError)?
+            .into_iter()
+            .filter(|trans| trans.transaction_type == TransactionType::Incoming)
+            .collect();
+
+        // Rows come back oldest-first; cap to the most recent
+        // `webhook_max_transactions` so a long-lived, heavily-paid invoice
+        // can't grow its callback body without bound.
+        let total_transactions = incoming_transactions.len();
+        public_invoice.transactions_truncated =
+            total_transactions > self.config.webhook_max_transactions;
+        if public_invoice.transactions_truncated {
+            incoming_transactions
+                .drain(..total_transactions - self.config.webhook_max_transactions);
+        }
+        public_invoice.transactions = incoming_transactions
+            .into_iter()
+            .map(From::from)
+            .collect();
+
         if invoice_status == InvoiceStatus::Paid {
+            // `Seen` and `Paid` currently fire together: transfers only ever
+            // reach this point already confirmed/finalized, so there's no
+            // earlier moment to report the balance being satisfied. The
+            // `seen_at` guard still matters: it keeps `Seen` from re-firing
+            // if a reorg later reverts `status` and the invoice is paid
+            // again.
+            if invoice.seen_at.is_none() {
+                dao_transaction
+                    .mark_invoice_seen(invoice_id)
+                    .await
+                    .map_err(|_e| TransactionsRecorderError::DaoTransactionError)?;
+
+                self.add_webhook_to_dao_transaction(
+                    &dao_transaction,
+                    public_invoice.clone(),
+                    InvoiceEventType::Seen,
+                )
+                .await?;
+            }
+
             // In case when invoice is just "Paid" without refund required,
             // put here total received amount which might be slightly higher or lower then
             // invoice amount
@@ -162,13 +219,33 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
             )
             .await?;
         } else if invoice_status == InvoiceStatus::PartiallyPaid {
-            self.add_webhook_to_dao_transaction(
-                &dao_transaction,
-                public_invoice,
-                InvoiceEventType::PartiallyPaid,
-            )
-            .await?;
+            // Only notify the first time the invoice dips into partial
+            // payment: subsequent still-insufficient increments already
+            // update the stored amount above, but would otherwise fire an
+            // identical `PartiallyPaid` webhook per transaction.
+            if previous_status != InvoiceStatus::PartiallyPaid {
+                self.add_webhook_to_dao_transaction(
+                    &dao_transaction,
+                    public_invoice,
+                    InvoiceEventType::PartiallyPaid,
+                )
+                .await?;
+            }
         } else if invoice_status == InvoiceStatus::OverPaid {
+            if invoice.seen_at.is_none() {
+                dao_transaction
+                    .mark_invoice_seen(invoice_id)
+                    .await
+                    .map_err(|_e| TransactionsRecorderError::DaoTransactionError)?;
+
+                self.add_webhook_to_dao_transaction(
+                    &dao_transaction,
+                    public_invoice.clone(),
+                    InvoiceEventType::Seen,
+                )
+                .await?;
+            }
+
             // In case when invoice is overpaid and refund is required, we schedule payout
             // with original invoice amount and refund with the rest amount
             let payout_amount = invoice.amount;
@@ -204,7 +281,16 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
         Ok(())
     }
 
-    #[tracing::instrument(skip_all)]
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            invoice_id = %invoice.invoice.id,
+            address = %invoice.invoice.payment_address,
+            chain = %transaction.transfer_info.chain,
+            asset_id = %transaction.transfer_info.asset_id,
+            block_number = ?transaction.transaction_id.block_number,
+        )
+    )]
     pub async fn process_invoice_transaction(
         &self,
         invoice: &mut InvoiceWithReceivedAmount,
@@ -220,6 +306,31 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
             ref mut total_received_amount,
         } = invoice;
 
+        if let Some(expected_sender) = &invoice.expected_sender {
+            if *expected_sender != transaction.transfer_info.source_address {
+                // Record it so it's visible to operators and can be refunded
+                // manually, but don't let it count toward the invoice's
+                // balance or change its status - the transaction's own
+                // recorded status is left unchanged.
+                tracing::warn!(
+                    invoice_id = %invoice.id,
+                    expected_sender = %expected_sender,
+                    actual_sender = %transaction.transfer_info.source_address,
+                    "Incoming transaction sender doesn't match invoice's expected sender, recording without crediting"
+                );
+
+                self.store_transaction(
+                    transaction,
+                    invoice.status,
+                    invoice.status,
+                    *total_received_amount,
+                )
+                .await?;
+
+                return Ok(());
+            }
+        }
+
         let updated_received_amount = *total_received_amount + transaction.transfer_info.amount;
 
         let underpayment_tolerance = self
@@ -243,9 +354,27 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
             InvoiceStatus::OverPaid
         };
 
+        // Makes it trivial to grep a production log for how a specific
+        // invoice evaluated across blocks, without having to cross-reference
+        // the `Ok(())` match arms below for the resulting status.
+        tracing::debug!(
+            balance = %updated_received_amount,
+            expected = %invoice.amount,
+            paid = matches!(updated_status, InvoiceStatus::Paid | InvoiceStatus::OverPaid),
+            "invoice {} at block {:?}: balance={} expected={} paid={}",
+            invoice.id,
+            transaction.transaction_id.block_number,
+            updated_received_amount,
+            invoice.amount,
+            matches!(updated_status, InvoiceStatus::Paid | InvoiceStatus::OverPaid),
+        );
+
+        let previous_status = invoice.status;
+
         match self
             .store_transaction(
                 transaction,
+                previous_status,
                 updated_status,
                 updated_received_amount,
             )
@@ -267,6 +396,9 @@ impl<D: DaoInterface + 'static> TransactionsRecorder<D> {
                 self.registry
                     .remove_invoice(&invoice.id)
                     .await;
+                self.registry
+                    .record_invoice_paid(invoice.id)
+                    .await;
 
                 invoice.status = updated_status;
                 *total_received_amount = updated_received_amount;
@@ -358,6 +490,7 @@ mod tests {
     };
     use crate::types::{
         Invoice,
+        Transaction,
         default_incoming_transaction,
         default_invoice,
     };
@@ -372,12 +505,18 @@ mod tests {
                 "0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359".to_string(),
             )]),
             invoice_lifetime_millis: 600_000,
+            expiration_check_interval_millis: 10_000,
             recipient: HashMap::from([(
                 ChainType::Polygon,
                 "0x0E3Ca7fD040144900AdaA5f9B8917f3933A4F5e9".to_string(),
             )]),
             payment_url_base: "https://payments.example.com".to_string(),
             slippage_params: HashMap::new(),
+            sweep_fee_buffer: HashMap::new(),
+            recent_events_buffer_size: 256,
+            minimum_invoice_amount: HashMap::new(),
+            webhook_max_transactions: 100,
+            max_watched_invoices: None,
         }
     }
 
@@ -408,6 +547,12 @@ mod tests {
             .with(eq(invoice_id), eq(status))
             .returning(move |_, _| Ok(returning_invoice.clone()));
 
+        dao_transaction
+            .expect_get_invoice_transactions()
+            .once()
+            .with(eq(invoice_id))
+            .returning(|_| Ok(vec![]));
+
         dao_transaction
             .expect_create_webhook_event()
             .once()
@@ -464,12 +609,44 @@ mod tests {
             .with(eq(invoice_id), eq(status))
             .returning(move |_, _| Ok(returning_invoice.clone()));
 
+        dao_transaction
+            .expect_get_invoice_transactions()
+            .once()
+            .with(eq(invoice_id))
+            .returning(|_| Ok(vec![]));
+
+        let seen_invoice = Invoice {
+            status,
+            ..invoice.clone()
+        };
+
+        dao_transaction
+            .expect_mark_invoice_seen()
+            .once()
+            .with(eq(invoice_id))
+            .returning(move |_| Ok(seen_invoice.clone()));
+
         dao_transaction
             .expect_create_payout()
             .once()
             .withf(move |p| p.amount == amount)
             .returning(Ok);
 
+        dao_transaction
+            .expect_create_webhook_event()
+            .once()
+            .withf(move |event| {
+                let generic_event: KalatoriEvent =
+                    serde_json::from_value(event.payload.clone()).unwrap();
+                #[expect(irrefutable_let_patterns)]
+                let KalatoriEvent::Invoice(invoice_event) = generic_event else {
+                    return false
+                };
+
+                invoice_event.event_type == InvoiceEventType::Seen && event.entity_id == invoice_id
+            })
+            .returning(Ok);
+
         dao_transaction
             .expect_create_webhook_event()
             .once()
@@ -527,6 +704,23 @@ mod tests {
             .with(eq(invoice_id), eq(status))
             .returning(move |_, _| Ok(returning_invoice.clone()));
 
+        dao_transaction
+            .expect_get_invoice_transactions()
+            .once()
+            .with(eq(invoice_id))
+            .returning(|_| Ok(vec![]));
+
+        let seen_invoice = Invoice {
+            status,
+            ..invoice.clone()
+        };
+
+        dao_transaction
+            .expect_mark_invoice_seen()
+            .once()
+            .with(eq(invoice_id))
+            .returning(move |_| Ok(seen_invoice.clone()));
+
         dao_transaction
             .expect_create_payout()
             .once()
@@ -539,6 +733,21 @@ mod tests {
             .once()
             .returning(Ok);
 
+        dao_transaction
+            .expect_create_webhook_event()
+            .once()
+            .withf(move |event| {
+                let generic_event: KalatoriEvent =
+                    serde_json::from_value(event.payload.clone()).unwrap();
+                #[expect(irrefutable_let_patterns)]
+                let KalatoriEvent::Invoice(invoice_event) = generic_event else {
+                    return false
+                };
+
+                invoice_event.event_type == InvoiceEventType::Seen && event.entity_id == invoice_id
+            })
+            .returning(Ok);
+
         dao_transaction
             .expect_create_webhook_event()
             .once()
@@ -578,7 +787,7 @@ mod tests {
             .clone()
             .with_amount(Decimal::ZERO);
 
-        let registry = InvoiceRegistry::new();
+        let registry = InvoiceRegistry::new(256);
         registry
             .add_invoice(invoice_with_amount)
             .await;
@@ -594,6 +803,7 @@ mod tests {
         //   - Webhook event created
         {
             // Setup test
+            let previous_status = InvoiceStatus::Waiting;
             let status = InvoiceStatus::PartiallyPaid;
             let transaction = default_incoming_transaction(invoice_id);
             // in this method it should only be included into event
@@ -610,7 +820,12 @@ mod tests {
 
             // Test and assert
             let result = recorder
-                .store_transaction(transaction, status, amount)
+                .store_transaction(
+                    transaction,
+                    previous_status,
+                    status,
+                    amount,
+                )
                 .await;
             // We need to ensure that we received successful result only, the rest checks
             // are made in dao mocks
@@ -641,7 +856,12 @@ mod tests {
 
             // Test and assert
             let result = recorder
-                .store_transaction(transaction, status, amount)
+                .store_transaction(
+                    transaction,
+                    InvoiceStatus::Waiting,
+                    status,
+                    amount,
+                )
                 .await;
             // We need to ensure that we received successful result only, the rest checks
             // are made in dao mocks
@@ -684,7 +904,12 @@ mod tests {
 
             // Test and assert
             let result = recorder
-                .store_transaction(transaction.clone(), status, amount)
+                .store_transaction(
+                    transaction.clone(),
+                    InvoiceStatus::Waiting,
+                    status,
+                    amount,
+                )
                 .await;
             // We need to ensure that we received successful result only, the rest checks
             // are made in dao mocks
@@ -727,7 +952,12 @@ mod tests {
 
             // Test and assert
             let result = recorder
-                .store_transaction(transaction.clone(), status, amount)
+                .store_transaction(
+                    transaction.clone(),
+                    InvoiceStatus::Waiting,
+                    status,
+                    amount,
+                )
                 .await;
             // We need to ensure that we received successful result only, the rest checks
             // are made in dao mocks
@@ -737,6 +967,173 @@ mod tests {
                 TransactionsRecorderError::DaoTransactionError
             ));
         }
+
+        // Test case 5:
+        // - Successful flow
+        // - Invoice was already PartiallyPaid before this transaction
+        // - Expectations:
+        //   - Transaction created
+        //   - Invoice status updated
+        //   - No webhook event created (would be a duplicate `PartiallyPaid`
+        //     notification)
+        {
+            // Setup test
+            let previous_status = InvoiceStatus::PartiallyPaid;
+            let status = InvoiceStatus::PartiallyPaid;
+            let transaction = default_incoming_transaction(invoice_id);
+            let amount = Decimal::ONE_HUNDRED;
+
+            let returning_invoice = Invoice {
+                status,
+                ..invoice.clone()
+            };
+
+            let mut dao_transaction = MockDaoTransactionInterface::default();
+
+            dao_transaction
+                .expect_create_transaction()
+                .once()
+                .returning(Ok);
+
+            dao_transaction
+                .expect_update_invoice_status()
+                .once()
+                .with(eq(invoice_id), eq(status))
+                .returning(move |_, _| Ok(returning_invoice.clone()));
+
+            dao_transaction
+                .expect_get_invoice_transactions()
+                .once()
+                .with(eq(invoice_id))
+                .returning(|_| Ok(vec![]));
+
+            dao_transaction
+                .expect_commit()
+                .once()
+                .returning(|| Ok(()));
+
+            // No `expect_create_webhook_event` set up: mockall panics if it's
+            // called anyway, proving the duplicate notification is skipped.
+
+            recorder
+                .dao
+                .expect_begin_transaction()
+                .once()
+                .return_once(move || Ok(dao_transaction));
+
+            // Test and assert
+            let result = recorder
+                .store_transaction(
+                    transaction,
+                    previous_status,
+                    status,
+                    amount,
+                )
+                .await;
+            assert!(result.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_transaction_caps_webhook_transactions() {
+        // Invoice with more incoming transactions than
+        // `webhook_max_transactions` should only report the most recent ones
+        // in the webhook payload, with `transactions_truncated` set.
+        let config = PaymentsConfig {
+            webhook_max_transactions: 2,
+            ..default_payments_config()
+        };
+        let dao = MockDaoInterface::default();
+
+        let invoice = default_invoice();
+        let invoice_id = invoice.id;
+        let amount = Decimal::ONE_HUNDRED;
+
+        let all_transactions: Vec<Transaction> = (0..5)
+            .map(|_| default_incoming_transaction(invoice_id).into())
+            .collect();
+        let most_recent_transaction_ids: Vec<_> = all_transactions[3..]
+            .iter()
+            .map(|t| t.id)
+            .collect();
+
+        let returning_invoice = Invoice {
+            status: InvoiceStatus::PartiallyPaid,
+            ..invoice.clone()
+        };
+
+        let mut dao_transaction = MockDaoTransactionInterface::default();
+
+        dao_transaction
+            .expect_create_transaction()
+            .once()
+            .returning(Ok);
+
+        dao_transaction
+            .expect_update_invoice_status()
+            .once()
+            .with(
+                eq(invoice_id),
+                eq(InvoiceStatus::PartiallyPaid),
+            )
+            .returning(move |_, _| Ok(returning_invoice.clone()));
+
+        dao_transaction
+            .expect_get_invoice_transactions()
+            .once()
+            .with(eq(invoice_id))
+            .return_once(move |_| Ok(all_transactions));
+
+        dao_transaction
+            .expect_create_webhook_event()
+            .once()
+            .withf(move |event| {
+                let generic_event: KalatoriEvent =
+                    serde_json::from_value(event.payload.clone()).unwrap();
+                #[expect(irrefutable_let_patterns)]
+                let KalatoriEvent::Invoice(invoice_event) = generic_event else {
+                    return false
+                };
+
+                let transaction_ids: Vec<_> = invoice_event
+                    .payload
+                    .transactions
+                    .iter()
+                    .map(|t| t.id)
+                    .collect();
+
+                invoice_event.event_type == InvoiceEventType::PartiallyPaid
+                    && invoice_event
+                        .payload
+                        .transactions_truncated
+                    && transaction_ids == most_recent_transaction_ids
+            })
+            .returning(Ok);
+
+        dao_transaction
+            .expect_commit()
+            .once()
+            .returning(|| Ok(()));
+
+        let registry = InvoiceRegistry::new(256);
+        let mut recorder = TransactionsRecorder::new(dao, registry, config);
+
+        recorder
+            .dao
+            .expect_begin_transaction()
+            .once()
+            .return_once(move || Ok(dao_transaction));
+
+        let result = recorder
+            .store_transaction(
+                default_incoming_transaction(invoice_id),
+                InvoiceStatus::Waiting,
+                InvoiceStatus::PartiallyPaid,
+                amount,
+            )
+            .await;
+
+        assert!(result.is_ok());
     }
 
     #[tokio::test]
@@ -744,7 +1141,7 @@ mod tests {
         let config = default_payments_config();
         let dao = MockDaoInterface::default();
 
-        let registry = InvoiceRegistry::new();
+        let registry = InvoiceRegistry::new(256);
         let mut recorder = TransactionsRecorder::new(dao, registry.clone(), config);
 
         // Test case 1:
@@ -1199,4 +1596,94 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_process_invoice_transaction_rejects_unexpected_sender() {
+        let config = default_payments_config();
+        let dao = MockDaoInterface::default();
+
+        let registry = InvoiceRegistry::new(256);
+        let mut recorder = TransactionsRecorder::new(dao, registry.clone(), config);
+
+        let invoice = Invoice {
+            amount: Decimal::ONE_THOUSAND,
+            expected_sender: Some("0x0000000000000000000000000000000000000001".to_string()),
+            ..default_invoice()
+        };
+        let invoice_id = invoice.id;
+        let mut invoice_with_amount = invoice.with_amount(Decimal::ZERO);
+
+        registry
+            .add_invoice(invoice_with_amount.clone())
+            .await;
+
+        // Sent from a different address than the invoice expects.
+        let transaction = default_incoming_transaction(invoice_id);
+
+        let mut dao_transaction = MockDaoTransactionInterface::default();
+
+        dao_transaction
+            .expect_create_transaction()
+            .once()
+            .returning(Ok);
+
+        let returning_invoice = invoice_with_amount.invoice.clone();
+        dao_transaction
+            .expect_update_invoice_status()
+            .once()
+            .with(
+                eq(invoice_id),
+                eq(InvoiceStatus::Waiting),
+            )
+            .returning(move |_, status| {
+                Ok(Invoice {
+                    status,
+                    ..returning_invoice.clone()
+                })
+            });
+
+        dao_transaction
+            .expect_get_invoice_transactions()
+            .once()
+            .with(eq(invoice_id))
+            .returning(|_| Ok(vec![]));
+
+        dao_transaction
+            .expect_commit()
+            .once()
+            .returning(|| Ok(()));
+
+        recorder
+            .dao
+            .expect_begin_transaction()
+            .once()
+            .return_once(move || Ok(dao_transaction));
+
+        let result = recorder
+            .process_invoice_transaction(&mut invoice_with_amount, transaction)
+            .await;
+        assert!(result.is_ok());
+
+        // Neither the status nor the received amount moved: the mismatched
+        // sender's transfer was recorded but not credited.
+        assert_eq!(
+            invoice_with_amount.invoice.status,
+            InvoiceStatus::Waiting
+        );
+        assert!(
+            invoice_with_amount
+                .total_received_amount
+                .is_zero()
+        );
+
+        let invoice_in_registry = registry
+            .get_invoice(&invoice_id)
+            .await
+            .unwrap();
+        assert!(
+            invoice_in_registry
+                .total_received_amount
+                .is_zero()
+        );
+    }
 }
@@ -2,6 +2,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use futures::StreamExt;
+use rand::Rng;
 use tokio_util::sync::CancellationToken;
 
 use crate::chain_client::{
@@ -25,6 +26,14 @@ const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
 const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
 const DEGRADED_WARNING_INTERVAL: Duration = Duration::from_secs(60);
 
+/// Apply equal jitter (half fixed, half random) to a backoff delay, so that
+/// trackers which all started failing at the same moment (e.g. a shared RPC
+/// endpoint going down) don't all retry in lockstep.
+fn jitter(delay: Duration) -> Duration {
+    let half = delay / 2;
+    half.saturating_add(rand::rng().random_range(Duration::ZERO..=half))
+}
+
 struct RetryState {
     delay: Duration,
     degraded_since: Option<tokio::time::Instant>,
@@ -75,7 +84,7 @@ impl RetryState {
             .delay
             .saturating_mul(2)
             .min(MAX_RETRY_DELAY);
-        delay
+        jitter(delay)
     }
 
     fn record_health(&mut self) {
@@ -404,7 +413,7 @@ mod tests {
             .expect_recreate()
             .returning(|| Err(ClientError::AllEndpointsUnreachable));
 
-        let registry = InvoiceRegistry::new();
+        let registry = InvoiceRegistry::new(256);
         let recorder = TransactionsRecorder::<DAO>::default();
         let tracker = TransfersTracker::new(chain_client, registry, recorder);
 
@@ -476,7 +485,7 @@ mod tests {
 
         let tracker = TransfersTracker::new(
             chain_client,
-            InvoiceRegistry::new(),
+            InvoiceRegistry::new(256),
             TransactionsRecorder::<DAO>::default(),
         );
         let token = CancellationToken::new();
@@ -555,7 +564,7 @@ mod tests {
 
         let tracker = TransfersTracker::new(
             chain_client,
-            InvoiceRegistry::new(),
+            InvoiceRegistry::new(256),
             TransactionsRecorder::<DAO>::default(),
         );
         let token = CancellationToken::new();
@@ -608,7 +617,7 @@ mod tests {
 
         let tracker = TransfersTracker::new(
             chain_client,
-            InvoiceRegistry::new(),
+            InvoiceRegistry::new(256),
             TransactionsRecorder::<DAO>::default(),
         );
         let token = CancellationToken::new();
@@ -624,27 +633,21 @@ mod tests {
 
     #[test]
     fn persistent_failures_back_off_exponentially_to_cap() {
-        let started_at = tokio::time::Instant::now();
-        let mut attempted_at = started_at;
         let mut retry_state = RetryState::new();
-        let expected_delays = [1, 2, 4, 8, 16, 32, 60, 60];
-
-        for expected_delay in expected_delays {
-            let delay = retry_state.record_failure_at(attempted_at);
-            assert_eq!(
-                delay,
-                Duration::from_secs(expected_delay)
+        let base_delays = [1, 2, 4, 8, 16, 32, 60, 60];
+
+        for base_delay in base_delays {
+            let base_delay = Duration::from_secs(base_delay);
+            let delay = retry_state.record_failure_at(tokio::time::Instant::now());
+            assert!(
+                delay >= base_delay / 2 && delay <= base_delay,
+                "jittered delay {delay:?} should be within half of base delay {base_delay:?}"
             );
-            attempted_at += delay;
         }
 
         assert_eq!(
             retry_state.attempts,
-            expected_delays.len() as u64
-        );
-        assert_eq!(
-            attempted_at.duration_since(started_at),
-            Duration::from_secs(183)
+            base_delays.len() as u64
         );
     }
 
@@ -682,19 +685,17 @@ mod tests {
         let started_at = tokio::time::Instant::now();
         let mut retry_state = RetryState::new();
 
-        assert_eq!(
-            retry_state.record_failure_at(started_at),
-            Duration::from_secs(1)
-        );
-        assert_eq!(
-            retry_state.record_failure_at(started_at),
-            Duration::from_secs(2)
-        );
+        let first_delay = retry_state.record_failure_at(started_at);
+        assert!(first_delay >= Duration::from_millis(500) && first_delay <= Duration::from_secs(1));
+        let second_delay = retry_state.record_failure_at(started_at);
+        assert!(second_delay >= Duration::from_secs(1) && second_delay <= Duration::from_secs(2));
         retry_state.record_health_at(started_at + Duration::from_secs(10));
 
-        assert_eq!(
-            retry_state.record_failure_at(started_at + Duration::from_secs(10)),
-            Duration::from_secs(1)
+        let post_recovery_delay =
+            retry_state.record_failure_at(started_at + Duration::from_secs(10));
+        assert!(
+            post_recovery_delay >= Duration::from_millis(500)
+                && post_recovery_delay <= Duration::from_secs(1)
         );
         assert!(logs_contain(
             "Transfer tracking recovered"
@@ -725,7 +726,7 @@ mod tests {
         // we can check log records to ensure the code is following
         // expected flows
         let chain_client = MockBlockChainClient::<PolygonChainConfig>::default();
-        let registry = InvoiceRegistry::new();
+        let registry = InvoiceRegistry::new(256);
         let recorder = TransactionsRecorder::<DAO>::default();
         let mut tracker = TransfersTracker::new(chain_client, registry.clone(), recorder);
 
@@ -859,7 +860,7 @@ mod tests {
     #[tokio::test]
     async fn test_handle_subscription_event() {
         let chain_client = MockBlockChainClient::<AssetHubChainConfig>::default();
-        let registry = InvoiceRegistry::new();
+        let registry = InvoiceRegistry::new(256);
         let recorder = TransactionsRecorder::<DAO>::default();
         let mut tracker = TransfersTracker::new(chain_client, registry.clone(), recorder);
 
@@ -1,8 +1,22 @@
-use base58::ToBase58;
+use base58::{
+    FromBase58,
+    ToBase58,
+};
+
+use crate::types::ChainType;
 
 // TODO: use something similar from separate crate?
 pub const HASH_512_LEN: usize = 64;
 pub const BASE58_ID: &[u8] = b"SS58PRE";
+const ACCOUNT_ID_LEN: usize = 32;
+const CHECKSUM_LEN: usize = 2;
+
+/// SS58 network prefix this daemon renders Polkadot Asset Hub addresses
+/// with. We only ever serve Polkadot (not Kusama, Westend, or generic
+/// Substrate chains — see `PaymentsConfig::validate_recipients`, which
+/// rejects a configured recipient encoded for any other network), so this is
+/// intentionally a constant rather than a per-chain config value.
+pub const POLKADOT_SS58_PREFIX: u16 = 0;
 
 pub fn ss58hash(data: &[u8]) -> [u8; HASH_512_LEN] {
     let mut blake2b_state = blake2b_simd::Params::new()
@@ -44,3 +58,252 @@ pub fn to_base58_string(
     v.extend(&r[0..2]);
     v.to_base58()
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum FromBase58StringError {
+    #[error("address is not valid base58")]
+    InvalidBase58,
+    #[error("address has an unexpected length for an SS58 address")]
+    InvalidLength,
+    #[error("address checksum doesn't match its payload")]
+    InvalidChecksum,
+}
+
+/// Inverse of [`to_base58_string`]: decode an SS58 address into its network
+/// prefix and the 32-byte account id it encodes, verifying the checksum.
+pub fn from_base58_string(address: &str) -> Result<(u16, [u8; 32]), FromBase58StringError> {
+    let data = address
+        .from_base58()
+        .map_err(|_e| FromBase58StringError::InvalidBase58)?;
+
+    // One prefix byte for idents 0..=63, two for 64..=16383 (see
+    // `to_base58_string`), followed by the 32-byte account id and a 2-byte
+    // checksum.
+    let prefix_len = match data.first() {
+        Some(0..=63) => 1,
+        Some(64..=255) => 2,
+        None => return Err(FromBase58StringError::InvalidLength),
+    };
+
+    if data.len() != prefix_len + ACCOUNT_ID_LEN + CHECKSUM_LEN {
+        return Err(FromBase58StringError::InvalidLength);
+    }
+
+    let (payload, checksum) = data.split_at(prefix_len + ACCOUNT_ID_LEN);
+    let expected_checksum = ss58hash(payload);
+    if checksum != &expected_checksum[0..CHECKSUM_LEN] {
+        return Err(FromBase58StringError::InvalidChecksum);
+    }
+
+    let ident = match prefix_len {
+        1 => u16::from(payload[0]),
+        _ => {
+            let low_upper_six = payload[0] & 0b0011_1111;
+            let bottom_two = (payload[1] & 0b1100_0000) >> 6;
+            let high_byte = payload[1] & 0b0011_1111;
+            let low_byte = (low_upper_six << 2) | bottom_two;
+            (u16::from(high_byte) << 8) | u16::from(low_byte)
+        },
+    };
+
+    let account_id: [u8; ACCOUNT_ID_LEN] = payload[prefix_len..]
+        .try_into()
+        .expect("payload length was just validated above");
+
+    Ok((ident, account_id))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AddressValidationError {
+    #[error("invalid Polkadot address: {0}")]
+    InvalidPolkadotAddress(FromBase58StringError),
+    #[error("address is encoded for SS58 network {found} instead of Polkadot (0)")]
+    WrongSs58Network { found: u16 },
+    #[error("invalid Polygon address")]
+    InvalidPolygonAddress,
+}
+
+impl crate::api::ApiErrorExt for AddressValidationError {
+    fn category(&self) -> &str {
+        "VALIDATION_ERROR"
+    }
+
+    fn code(&self) -> &str {
+        "INVALID_ADDRESS"
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            AddressValidationError::InvalidPolkadotAddress(_) => {
+                "The address is not a valid Polkadot address."
+            },
+            AddressValidationError::WrongSs58Network {
+                ..
+            } => "The address is encoded for a different SS58 network than Polkadot.",
+            AddressValidationError::InvalidPolygonAddress => {
+                "The address is not a valid Polygon address."
+            },
+        }
+    }
+
+    fn http_status_code(&self) -> axum::http::StatusCode {
+        axum::http::StatusCode::BAD_REQUEST
+    }
+}
+
+/// Validate a user-entered address for `chain`, reusing the exact parsing
+/// used on recipient addresses at config load time (see
+/// [`crate::configs::PaymentsConfig::validate_recipients`]), so a
+/// cheap pre-flight check sees the same rules as actual invoice registration.
+/// Returns the address re-encoded in the canonical form this daemon expects.
+pub fn validate_address(
+    chain: ChainType,
+    address: &str,
+) -> Result<String, AddressValidationError> {
+    match chain {
+        ChainType::PolkadotAssetHub => {
+            let (found_prefix, account_id) = from_base58_string(address)
+                .map_err(AddressValidationError::InvalidPolkadotAddress)?;
+
+            if found_prefix != POLKADOT_SS58_PREFIX {
+                return Err(
+                    AddressValidationError::WrongSs58Network {
+                        found: found_prefix,
+                    },
+                );
+            }
+
+            Ok(to_base58_string(
+                account_id,
+                POLKADOT_SS58_PREFIX,
+            ))
+        },
+        ChainType::Polygon => {
+            let parsed = address
+                .parse::<alloy::primitives::Address>()
+                .map_err(|_e| AddressValidationError::InvalidPolygonAddress)?;
+
+            Ok(parsed.to_checksum(None))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_one_byte_prefix() {
+        let account_id = [7u8; 32];
+        let address = to_base58_string(account_id, 0);
+
+        assert_eq!(
+            from_base58_string(&address),
+            Ok((0, account_id))
+        );
+    }
+
+    #[test]
+    fn round_trips_two_byte_prefix() {
+        let account_id = [9u8; 32];
+        let address = to_base58_string(account_id, 1_000);
+
+        assert_eq!(
+            from_base58_string(&address),
+            Ok((1_000, account_id))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_base58() {
+        assert_eq!(
+            from_base58_string("not-base58!"),
+            Err(FromBase58StringError::InvalidBase58)
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_length() {
+        let address = bs58_encode_raw(&[0u8; 10]);
+
+        assert_eq!(
+            from_base58_string(&address),
+            Err(FromBase58StringError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_checksum() {
+        let address = to_base58_string([1u8; 32], 0);
+        let mut data = address.from_base58().unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+
+        assert_eq!(
+            from_base58_string(&data.to_base58()),
+            Err(FromBase58StringError::InvalidChecksum)
+        );
+    }
+
+    fn bs58_encode_raw(bytes: &[u8]) -> String {
+        bytes.to_base58()
+    }
+
+    #[test]
+    fn validates_polkadot_address() {
+        let address = to_base58_string([3u8; 32], 0);
+
+        assert_eq!(
+            validate_address(ChainType::PolkadotAssetHub, &address),
+            Ok(address)
+        );
+    }
+
+    #[test]
+    fn rejects_polkadot_address_from_wrong_network() {
+        let address = to_base58_string([3u8; 32], 2);
+
+        assert_eq!(
+            validate_address(ChainType::PolkadotAssetHub, &address),
+            Err(
+                AddressValidationError::WrongSs58Network {
+                    found: 2
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_polkadot_address() {
+        assert_eq!(
+            validate_address(
+                ChainType::PolkadotAssetHub,
+                "not-an-address"
+            ),
+            Err(
+                AddressValidationError::InvalidPolkadotAddress(
+                    FromBase58StringError::InvalidBase58
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn validates_polygon_address() {
+        assert_eq!(
+            validate_address(
+                ChainType::Polygon,
+                "0x3c499c542cef5e3811e1192ce70d8cc03d5c3359"
+            ),
+            Ok("0x3c499c542cEF5E3811e1192ce70d8cC03d5c3359".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_polygon_address() {
+        assert_eq!(
+            validate_address(ChainType::Polygon, "not-an-address"),
+            Err(AddressValidationError::InvalidPolygonAddress)
+        );
+    }
+}
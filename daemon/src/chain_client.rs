@@ -25,6 +25,7 @@ use crate::types::{
 };
 
 pub use asset_hub::{
+    AssetHubAccountId,
     AssetHubChainConfig,
     AssetHubClient,
 };
@@ -73,6 +74,12 @@ pub trait ChainConfig: Clone + std::fmt::Debug + Sync + Send + 'static {
         + Sync
         + Send;
     type TransactionHash: FromStr + ToString + Sync + Send;
+    // `Serialize`/`Deserialize` are intentionally not required here: concrete
+    // bindings (subxt's `H256` for Asset Hub, `alloy::primitives::B256` for
+    // Polygon) already implement both upstream, with hex round-tripping
+    // equivalent to `FromStr`/`ToString`. Callers that need to put a block
+    // hash in a JSON config or API response should use the concrete type's
+    // own derive rather than adding a serde bound here.
     type BlockHash: FromStr + ToString + Sync + Send;
     type UnsignedTransaction: Send + std::fmt::Debug + PartialEq;
     type SignedTransaction: SignedTransactionUtils + Sync + Send + std::fmt::Debug + PartialEq;
@@ -86,6 +93,130 @@ pub struct AssetInfo<T: ChainConfig> {
     pub name: String,
     pub id: T::AssetId,
     pub decimals: u8,
+    /// Minimum balance the asset's own rules allow an account to hold before
+    /// it's dusted. Zero for chains/assets without such a rule (e.g.
+    /// Polygon's ERC-20 tokens). Lets invoice and sweep logic flag an amount
+    /// that would leave the payment address below it.
+    pub min_balance: Decimal,
+}
+
+/// The most recently processed block a chain client has actually ingested
+/// while subscribing for transfers. Distinct from querying the RPC endpoint
+/// directly for its current head: this reflects what the watcher itself has
+/// gotten through, which is what determines invoice evaluation latency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainTip {
+    pub block_number: u32,
+    pub block_hash: String,
+    /// Milliseconds since epoch. The Asset Hub client reads it from the
+    /// chain's own clock (see `fetch_block_timestamp` in
+    /// `chain_client/asset_hub.rs`), falling back to local wall time if that
+    /// read fails; the Polygon client records local wall time when it
+    /// ingests a log.
+    pub timestamp: u64,
+}
+
+/// Smoothing factor for the block time EMA in [`ChainTipTracker`]. Lower
+/// values track the long-run average more closely; this value reacts within
+/// a handful of blocks to a genuine change in block production rate while
+/// still absorbing ordinary jitter.
+const BLOCK_TIME_EMA_ALPHA: f64 = 0.2;
+
+/// An inter-block gap this many times longer than the current estimate is
+/// treated as an RPC/watcher gap rather than a real slowdown, and excluded
+/// from the EMA so one missed block subscription doesn't permanently skew
+/// the estimate.
+const BLOCK_TIME_OUTLIER_FACTOR: u64 = 5;
+
+#[derive(Debug, Clone)]
+struct ChainTipState {
+    tip: ChainTip,
+    /// Exponential moving average of inter-block durations, in milliseconds.
+    /// `None` until a second block has been observed.
+    block_time_estimate_millis: Option<u64>,
+}
+
+/// Cheap handle shared by every clone of a [`BlockChainClient`], so the copy
+/// held by its `TransfersTracker` and the copy held by `BalanceChecker` (or
+/// any other holder) observe the same tip as soon as it's updated. Also
+/// maintains an EMA of inter-block durations from observed tip timestamps,
+/// so the API can translate a confirmation depth into an estimated
+/// wall-clock time without assuming a fixed block time per chain.
+#[derive(Clone, Default)]
+pub struct ChainTipTracker(Arc<RwLock<Option<ChainTipState>>>);
+
+impl ChainTipTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self) -> Option<ChainTip> {
+        self.0
+            .read()
+            .await
+            .as_ref()
+            .map(|state| state.tip.clone())
+    }
+
+    /// Current block time estimate, or `None` until at least two blocks have
+    /// been observed.
+    pub async fn block_time_estimate_millis(&self) -> Option<u64> {
+        self.0
+            .read()
+            .await
+            .as_ref()
+            .and_then(|state| state.block_time_estimate_millis)
+    }
+
+    pub async fn set(
+        &self,
+        tip: ChainTip,
+    ) {
+        let mut guard = self.0.write().await;
+
+        let block_time_estimate_millis = match guard.as_ref() {
+            Some(previous) => {
+                let delta = tip
+                    .timestamp
+                    .saturating_sub(previous.tip.timestamp);
+
+                match previous.block_time_estimate_millis {
+                    // Don't let one long gap (a missed subscription event, an
+                    // RPC reconnect) drag the estimate up; just keep the
+                    // previous value and wait for the next, hopefully normal,
+                    // interval.
+                    Some(estimate)
+                        if delta > estimate.saturating_mul(BLOCK_TIME_OUTLIER_FACTOR) =>
+                    {
+                        Some(estimate)
+                    },
+                    Some(estimate) => Some(ema(estimate, delta)),
+                    // Second observed block: seed the EMA directly with the
+                    // first measured interval.
+                    None => Some(delta),
+                }
+            },
+            None => None,
+        };
+
+        *guard = Some(ChainTipState {
+            tip,
+            block_time_estimate_millis,
+        });
+    }
+}
+
+#[expect(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn ema(
+    previous: u64,
+    sample: u64,
+) -> u64 {
+    let blended = BLOCK_TIME_EMA_ALPHA.mul_add(
+        sample as f64,
+        (1.0 - BLOCK_TIME_EMA_ALPHA) * previous as f64,
+    );
+
+    blended.round() as u64
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -210,14 +341,54 @@ impl<T: ChainConfig> AssetInfoStore<T> {
             .collect()
     }
 
-    pub async fn asset_names_map(&self) -> HashMap<String, String> {
+    /// Asset names keyed by `(chain, asset_id)` rather than the bare asset ID
+    /// string, so merging this with another chain's map (see call site in
+    /// `main.rs`) can't silently collide two different chains' assets that
+    /// happen to share the same ID representation.
+    pub async fn asset_names_map(&self) -> HashMap<(ChainType, String), String> {
         let assets = self.assets.read().await;
 
         assets
             .iter()
-            .map(|(id, info)| (id.to_string(), info.name.clone()))
+            .map(|(id, info)| {
+                (
+                    (T::CHAIN_TYPE, id.to_string()),
+                    info.name.clone(),
+                )
+            })
             .collect()
     }
+
+    /// Look up an asset by its human-readable symbol (e.g. `"USDC"`) among
+    /// the assets fetched from the chain at startup. Always reflects the
+    /// chain's own metadata, never a caller-supplied value, so it can't be
+    /// used to smuggle in mismatched decimals for a real asset.
+    pub async fn get_asset_info_by_name(
+        &self,
+        name: &str,
+    ) -> Option<AssetInfo<T>> {
+        let assets = self.assets.read().await;
+        assets
+            .values()
+            .find(|info| info.name == name)
+            .cloned()
+    }
+}
+
+/// How much of a sweep source account's balance a
+/// [`BlockChainClient::build_transfer_all`] call should move out.
+///
+/// Defaults to [`Self::KeepAlive`]: on chains with an existential deposit
+/// (e.g. Asset Hub), taking the entire balance lets the source account die
+/// and re-surface a reap fee on its next incoming payment, which would
+/// surprise a merchant who didn't ask for that. Chains without an
+/// existential deposit concept (e.g. Polygon) ignore this and always sweep
+/// the full balance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SweepMode {
+    #[default]
+    KeepAlive,
+    All,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -283,6 +454,19 @@ pub trait BlockChainClient<T: ChainConfig>: Sync {
         asset_ids: &[T::AssetId],
     ) -> Result<TransfersStream<T>, SubscriptionError>;
 
+    /// The last block this client has actually ingested while subscribed for
+    /// transfers, for clients and dashboards wanting to measure invoice
+    /// evaluation latency. `None` until the first block has been processed
+    /// after startup.
+    async fn chain_tip(&self) -> Option<ChainTip>;
+
+    /// EMA of this chain's inter-block duration, computed from observed tip
+    /// timestamps (see [`ChainTipTracker`]). `None` until at least two
+    /// blocks have been processed after startup. Lets the API translate a
+    /// confirmation depth into an estimated wall-clock time without assuming
+    /// a fixed block time per chain.
+    async fn block_time_estimate_millis(&self) -> Option<u64>;
+
     /// Build transaction to transfer exact amount to recipient
     async fn build_transfer(
         &self,
@@ -294,12 +478,14 @@ pub trait BlockChainClient<T: ChainConfig>: Sync {
 
     #[expect(dead_code)]
     /// Build transaction to sweep entire balance (all funds minus fees) to
-    /// recipient
+    /// recipient. `mode` controls whether the source account is allowed to
+    /// die in the process; see [`SweepMode`].
     async fn build_transfer_all(
         &self,
         sender: T::AccountId,
         recipient: T::AccountId,
         asset_id: T::AssetId,
+        mode: SweepMode,
     ) -> Result<UnsignedTransaction<T>, TransactionError<T>>;
 
     async fn sign_transaction(
@@ -309,9 +495,14 @@ pub trait BlockChainClient<T: ChainConfig>: Sync {
         keyring_client: &KeyringClient,
     ) -> Result<SignedTransaction<T>, TransactionError<T>>;
 
+    /// Submit a signed transaction and wait for finalization. `sender` is the
+    /// account that signed it, passed through so a stale/future nonce error
+    /// can trigger a nonce resync for that specific account; see
+    /// [`crate::chain_client::errors::is_stale_or_future_nonce_error`].
     async fn submit_and_watch_transaction(
         &self,
         transaction: SignedTransaction<T>,
+        sender: T::AccountId,
     ) -> Result<ChainTransfer<T>, TransactionError<T>>;
 
     // This method should be called at the very start of the program, right after
@@ -345,6 +536,13 @@ pub trait BlockChainClientExt<T: ChainConfig>: BlockChainClient<T> {
                 .await
                 .map_err(|_e| ClientError::MetadataFetchFailed)?;
 
+            info!(
+                asset_id = %id.to_string(),
+                asset_name = %asset_info.name,
+                decimals = asset_info.decimals,
+                "Asset decimals auto-populated from chain metadata"
+            );
+
             store.insert(id.clone(), asset_info);
         }
 
@@ -369,7 +567,214 @@ mod tests {
         let mut client = MockBlockChainClient::<AssetHubChainConfig>::default();
         client
             .expect_build_transfer_all()
-            .returning(|_, _, _| panic!("Unexpected"))
+            .returning(|_, _, _, _| panic!("Unexpected"))
             .times(0);
     }
+
+    fn tip(
+        block_number: u32,
+        timestamp: u64,
+    ) -> ChainTip {
+        ChainTip {
+            block_number,
+            block_hash: format!("0x{block_number}"),
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn block_time_estimate_is_none_until_second_block() {
+        let tracker = ChainTipTracker::new();
+
+        assert_eq!(
+            tracker
+                .block_time_estimate_millis()
+                .await,
+            None
+        );
+
+        tracker.set(tip(1, 1_000)).await;
+        assert_eq!(
+            tracker
+                .block_time_estimate_millis()
+                .await,
+            None
+        );
+
+        tracker.set(tip(2, 7_000)).await;
+        assert_eq!(
+            tracker
+                .block_time_estimate_millis()
+                .await,
+            Some(6_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn block_time_estimate_converges_toward_steady_interval() {
+        let tracker = ChainTipTracker::new();
+        let mut timestamp = 0;
+
+        for block_number in 1..=20 {
+            tracker
+                .set(tip(block_number, timestamp))
+                .await;
+            timestamp += 6_000;
+        }
+
+        let estimate = tracker
+            .block_time_estimate_millis()
+            .await
+            .unwrap();
+
+        // Not required to be exact, but should have converged close to the
+        // steady 6s interval after enough blocks.
+        assert!(
+            estimate.abs_diff(6_000) < 100,
+            "estimate {estimate} didn't converge to ~6000ms"
+        );
+    }
+
+    #[tokio::test]
+    async fn block_time_estimate_ignores_one_long_gap() {
+        let tracker = ChainTipTracker::new();
+
+        tracker.set(tip(1, 0)).await;
+        tracker.set(tip(2, 6_000)).await;
+        let before_gap = tracker
+            .block_time_estimate_millis()
+            .await
+            .unwrap();
+
+        // A long RPC gap shouldn't drag the estimate up.
+        tracker
+            .set(tip(3, 6_000 + 120_000))
+            .await;
+        assert_eq!(
+            tracker
+                .block_time_estimate_millis()
+                .await,
+            Some(before_gap)
+        );
+
+        // Normal spacing resumes right after.
+        tracker
+            .set(tip(4, 6_000 + 120_000 + 6_000))
+            .await;
+        assert!(
+            tracker
+                .block_time_estimate_millis()
+                .await
+                .unwrap()
+                < before_gap.saturating_mul(2)
+        );
+    }
+
+    #[test]
+    fn sweep_mode_defaults_to_keep_alive() {
+        assert_eq!(
+            SweepMode::default(),
+            SweepMode::KeepAlive
+        );
+        assert_ne!(SweepMode::default(), SweepMode::All);
+    }
+
+    #[tokio::test]
+    async fn test_get_asset_info_by_name() {
+        let store = AssetInfoStore::<AssetHubChainConfig>::new();
+        let asset_info = AssetInfo {
+            name: "USDC".to_string(),
+            id: 1337,
+            decimals: 6,
+            min_balance: Decimal::ZERO,
+        };
+
+        store
+            .assets
+            .write()
+            .await
+            .insert(asset_info.id, asset_info.clone());
+
+        assert_eq!(
+            store
+                .get_asset_info_by_name("USDC")
+                .await,
+            Some(asset_info)
+        );
+        assert_eq!(
+            store
+                .get_asset_info_by_name("nonexistent")
+                .await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_asset_names_map_is_keyed_by_chain_and_id() {
+        let store = AssetInfoStore::<AssetHubChainConfig>::new();
+        store
+            .assets
+            .write()
+            .await
+            .insert(1337, usdc_asset_info());
+
+        let names_map = store.asset_names_map().await;
+
+        assert_eq!(
+            names_map
+                .get(&(
+                    ChainType::PolkadotAssetHub,
+                    "1337".to_string()
+                ))
+                .map(String::as_str),
+            Some("USDC")
+        );
+    }
+
+    #[test]
+    fn test_asset_names_from_different_chains_with_the_same_id_do_not_collide() {
+        // Two chains could, in principle, both have an asset whose ID
+        // stringifies to "1337" (Asset Hub asset IDs are raw `u32`s). Merging
+        // their name maps (as `main.rs` does at startup) must not let one
+        // overwrite the other, which is exactly what keying by the bare ID
+        // string instead of `(ChainType, id)` would do.
+        let mut names_map = HashMap::from([(
+            (
+                ChainType::PolkadotAssetHub,
+                "1337".to_string(),
+            ),
+            "USDC".to_string(),
+        )]);
+
+        names_map.extend([(
+            (ChainType::Polygon, "1337".to_string()),
+            "SomeOtherToken".to_string(),
+        )]);
+
+        assert_eq!(names_map.len(), 2);
+        assert_eq!(
+            names_map
+                .get(&(
+                    ChainType::PolkadotAssetHub,
+                    "1337".to_string()
+                ))
+                .map(String::as_str),
+            Some("USDC")
+        );
+        assert_eq!(
+            names_map
+                .get(&(ChainType::Polygon, "1337".to_string()))
+                .map(String::as_str),
+            Some("SomeOtherToken")
+        );
+    }
+
+    fn usdc_asset_info() -> AssetInfo<AssetHubChainConfig> {
+        AssetInfo {
+            name: "USDC".to_string(),
+            id: 1337,
+            decimals: 6,
+            min_balance: Decimal::ZERO,
+        }
+    }
 }
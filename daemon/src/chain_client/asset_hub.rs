@@ -1,4 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{
+    BTreeMap,
+    HashMap,
+    HashSet,
+};
+use std::sync::Arc;
+use std::time::Duration;
 
 use futures::{
     StreamExt,
@@ -18,11 +24,16 @@ use subxt::config::{
     DefaultExtrinsicParamsBuilder,
     ExtrinsicParams,
 };
+use subxt::events::Phase;
+use subxt::ext::scale_value::At;
+use subxt::metadata::DecodeWithMetadata;
+use subxt::storage::Address as _;
 use subxt::utils::H256;
 use subxt::{
     Config,
     SubstrateConfig,
 };
+use tokio::sync::RwLock;
 use tracing::{
     debug,
     instrument,
@@ -37,6 +48,8 @@ use super::{
     BlockChainClient,
     BlockChainClientExt,
     ChainConfig,
+    ChainTip,
+    ChainTipTracker,
     ChainTransfer,
     ClientError,
     GeneralTransactionId,
@@ -45,12 +58,17 @@ use super::{
     SignedTransaction,
     SignedTransactionUtils,
     SubscriptionError,
+    SweepMode,
     TransactionError,
     TransfersStream,
     UnsignedTransaction,
 };
 
-use super::errors::is_insufficient_balance_error;
+use super::errors::{
+    is_insufficient_balance_error,
+    is_metadata_decode_error,
+    is_stale_or_future_nonce_error,
+};
 use super::keyring::SignTransactionRequestData;
 
 #[subxt::subxt(
@@ -71,6 +89,11 @@ use runtime::runtime_types::xcm::v3::junctions::Junctions;
 const DEFAULT_MORTALITY: u64 = 32;
 const DEFAULT_MULTILOCATION_PARENTS: u8 = 0;
 const DEFAULT_PALLET_INSTANCE: u8 = 50;
+/// How often [`AssetHubClient::poll_finalized_blocks`] asks for the latest
+/// finalized block when [`crate::configs::BlockSource::Polling`] is
+/// configured. Roughly Asset Hub's block time, so polling doesn't lag a
+/// subscription by much while still avoiding most redundant requests.
+const BLOCK_POLL_INTERVAL: Duration = Duration::from_secs(6);
 
 // We don't need to construct this at runtime, so an empty enum is appropriate.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -239,58 +262,170 @@ impl AnyTransferExtrinsic {
     }
 }
 
+/// Per-account nonce cache for outgoing extrinsics.
+///
+/// `subxt` fetches an account's nonce from the latest finalized block on
+/// every `create_partial` call, which doesn't account for transactions still
+/// sitting in the pool: concurrent sweeps from the same signing account (e.g.
+/// several invoices settling in the same block) would otherwise all fetch
+/// the same nonce and collide. This tracker fetches the nonce once per
+/// account and increments it locally for each subsequent extrinsic, with
+/// [`NonceTracker::resync`] dropping the cached value so the next call
+/// re-fetches from chain after a stale/future nonce error.
+///
+/// The lock is held across the on-chain fetch so concurrent callers for the
+/// same account can't both observe a cache miss and fetch (and thus reuse)
+/// the same nonce.
+#[derive(Debug, Clone, Default)]
+struct NonceTracker {
+    nonces: Arc<RwLock<BTreeMap<AssetHubAccountId, u64>>>,
+}
+
+impl NonceTracker {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    async fn next_nonce(
+        &self,
+        account: &AssetHubAccountId,
+        client: &SubxtAssetHubClient,
+    ) -> Result<u64, subxt::Error> {
+        let mut nonces = self.nonces.write().await;
+
+        if let Some(nonce) = nonces.get_mut(account) {
+            let next = *nonce;
+            *nonce = nonce.saturating_add(1);
+            return Ok(next);
+        }
+
+        let fetched = client
+            .tx()
+            .account_nonce(account)
+            .await?;
+        nonces.insert(
+            account.clone(),
+            fetched.saturating_add(1),
+        );
+        Ok(fetched)
+    }
+
+    /// Drop the cached nonce for `account`, forcing the next
+    /// [`NonceTracker::next_nonce`] call to re-fetch from chain. Called after
+    /// a stale/future nonce RPC error, which means the local cache has
+    /// drifted from the chain's view (e.g. another process used the same
+    /// seed, or a submitted extrinsic was dropped from the pool).
+    async fn resync(
+        &self,
+        account: &AssetHubAccountId,
+    ) {
+        self.nonces
+            .write()
+            .await
+            .remove(account);
+    }
+}
+
 #[derive(Clone)]
 pub struct AssetHubClient {
     config: crate::configs::ChainConfig,
     client: SubxtAssetHubClient,
     asset_info_store: AssetInfoStore<AssetHubChainConfig>,
+    nonce_tracker: NonceTracker,
+    connected_endpoint: String,
+    chain_tip: ChainTipTracker,
 }
 
 impl AssetHubClient {
-    #[instrument(skip(config, asset_info_store))]
+    #[instrument(skip(config, asset_info_store, nonce_tracker, chain_tip))]
     async fn from_config(
         config: &crate::configs::ChainConfig,
         asset_info_store: AssetInfoStore<AssetHubChainConfig>,
+        nonce_tracker: NonceTracker,
+        chain_tip: ChainTipTracker,
     ) -> Result<Self, ClientError> {
         // TODO: implement circuit breaker for endpoints
         // (should be another wrapper structure with endpoints hidden behind sync
         // primitives with error counters and usage timeouts)
-        let endpoint = config
-            .get_random_requests_endpoint()
-            .ok_or(ClientError::InvalidConfiguration {
+        let endpoints = config.shuffled_requests_endpoints();
+
+        if endpoints.is_empty() {
+            return Err(ClientError::InvalidConfiguration {
                 field: "endpoints".to_string(),
-            })?;
+            });
+        }
 
-        tracing::debug!(
-            url = endpoint,
-            chain = %Self::chain_type(),
-            "Trying to connect to endpoint...",
-        );
+        let mut client = None;
 
-        let client = if config.allow_insecure_endpoints {
-            SubxtAssetHubClient::from_insecure_url(&endpoint).await
-        } else {
-            SubxtAssetHubClient::from_url(&endpoint).await
-        }
-        .inspect_err(|e| {
+        for endpoint in &endpoints {
             tracing::debug!(
-                error.category = crate::utils::logging::category::CHAIN_CLIENT,
-                error.operation = crate::utils::logging::operation::CONNECT_CLIENT,
-                error.source = ?e,
+                url = endpoint,
                 chain = %Self::chain_type(),
-                endpoint = %endpoint,
-                "Failed to connect to Asset Hub RPC endpoint"
+                "Trying to connect to endpoint...",
             );
-        })
-        .map_err(|_| ClientError::AllEndpointsUnreachable)?;
+
+            // TODO: if `config.fingerprint_for(endpoint)` is set, verify the
+            // endpoint's certificate against it and fail with
+            // `ClientError::CertificateMismatch` rather than connecting.
+            // subxt's `from_url`/`from_insecure_url` don't expose a hook into
+            // the underlying jsonrpsee WS transport's TLS verifier, so this
+            // needs a hand-built transport with a custom `rustls`
+            // certificate verifier before it can be enforced.
+            let connect_result = if config.allow_insecure_endpoints {
+                SubxtAssetHubClient::from_insecure_url(endpoint).await
+            } else {
+                SubxtAssetHubClient::from_url(endpoint).await
+            };
+
+            match connect_result {
+                Ok(connected_client) => {
+                    client = Some((endpoint.clone(), connected_client));
+                    break;
+                },
+                Err(e) => {
+                    tracing::debug!(
+                        error.category = crate::utils::logging::category::CHAIN_CLIENT,
+                        error.operation = crate::utils::logging::operation::CONNECT_CLIENT,
+                        error.source = ?e,
+                        chain = %Self::chain_type(),
+                        endpoint = %endpoint,
+                        "Failed to connect to Asset Hub RPC endpoint, trying next one"
+                    );
+                },
+            }
+        }
+
+        let (connected_endpoint, client) = client.ok_or(ClientError::AllEndpointsUnreachable)?;
 
         Ok(AssetHubClient {
             config: config.clone(),
             client,
             asset_info_store,
+            nonce_tracker,
+            connected_endpoint,
+            chain_tip,
         })
     }
 
+    /// The RPC endpoint this client is currently connected to, for operators
+    /// monitoring which endpoint is in use.
+    pub fn connected_endpoint(&self) -> &str {
+        &self.connected_endpoint
+    }
+
+    /// The `spec_version` of the runtime this client negotiated at connection
+    /// time, for operators confirming the watcher is talking to the chain
+    /// version its baked-in metadata (`runtime_metadata_path` above) was
+    /// generated from. Metadata itself isn't refetched or re-decoded at
+    /// runtime — it's generated at build time via `make
+    /// download-node-metadata-ci` — so a mismatch here means the binary needs
+    /// a metadata regen and rebuild, not a cache invalidation.
+    pub fn spec_version(&self) -> u32 {
+        self.client
+            .runtime_version()
+            .spec_version
+    }
+
     #[instrument(skip(self))]
     async fn fetch_block_by_hash(
         &self,
@@ -308,7 +443,37 @@ impl AssetHubClient {
                     "Failed to fetch finalized block information"
                 );
             })
-            .map_err(|_| QueryError::RpcRequestFailed)
+            .map_err(|_| QueryError::RpcRequestFailed {
+                endpoint: self.connected_endpoint.clone(),
+            })
+    }
+
+    /// Read the Timestamp pallet's `Now` storage entry for `block`, in
+    /// milliseconds since the Unix epoch. Lets callers compare against chain
+    /// time instead of the local wall clock, which can drift from it.
+    #[instrument(skip(self, block), fields(block_number = block.number()))]
+    async fn fetch_block_timestamp(
+        &self,
+        block: &Block<SubxtAssetHubConfig, SubxtAssetHubClient>,
+    ) -> Result<u64, QueryError> {
+        block
+            .storage()
+            .fetch(&runtime::storage().timestamp().now())
+            .await
+            .inspect_err(|e| {
+                tracing::debug!(
+                    error.category = crate::utils::logging::category::CHAIN_CLIENT,
+                    error.source = ?e,
+                    block_number = block.number(),
+                    "Failed to fetch block timestamp"
+                );
+            })
+            .map_err(|_e| QueryError::RpcRequestFailed {
+                endpoint: self.connected_endpoint.clone(),
+            })?
+            .ok_or_else(|| QueryError::NotFound {
+                query_type: format!("timestamp for block {}", block.number()),
+            })
     }
 
     #[instrument(skip(self, block, assets), fields(block_number = block.number()))]
@@ -320,26 +485,26 @@ impl AssetHubClient {
         // Implementation for processing a block
         let block_number = block.number();
 
-        // Extract timestamp from storage
-        let timestamp = match block
-            .storage()
-            .fetch(&runtime::storage().timestamp().now())
-            .await
-        {
-            Ok(Some(ts)) => ts,
-            #[expect(clippy::cast_sign_loss)]
-            Ok(None) => {
-                tracing::warn!("Block {block_number} missing timestamp, using 0");
-                // TODO: fix expects. Maybe just use `chrono::DateTime`?
-                chrono::Utc::now().timestamp_millis() as u64
-            },
+        let timestamp = match self.fetch_block_timestamp(&block).await {
+            Ok(ts) => ts,
             #[expect(clippy::cast_sign_loss)]
             Err(e) => {
                 tracing::warn!("Failed to fetch timestamp for block {block_number}: {e}");
+                // TODO: fix expects. Maybe just use `chrono::DateTime`?
                 chrono::Utc::now().timestamp_millis() as u64
             },
         };
 
+        // Record what the watcher has actually ingested so far, independent of
+        // whether this block contains any transfers we care about.
+        self.chain_tip
+            .set(ChainTip {
+                block_number,
+                block_hash: block.hash().to_string(),
+                timestamp,
+            })
+            .await;
+
         // Get extrinsics
         let extrinsics = match block.extrinsics().await {
             Ok(e) => e,
@@ -365,7 +530,14 @@ impl AssetHubClient {
             .filter_map(Result::ok)
             .map(AnyTransferExtrinsic::TransferAll);
 
-        let all_transfer_extrinsics = transfer_extrinsics.chain(transfer_all_extrinsics);
+        let all_transfer_extrinsics: Vec<_> = transfer_extrinsics
+            .chain(transfer_all_extrinsics)
+            .collect();
+
+        let known_extrinsic_indices: HashSet<u32> = all_transfer_extrinsics
+            .iter()
+            .map(|ext| ext.details().index())
+            .collect();
 
         let events = stream::iter(all_transfer_extrinsics)
             .filter_map(|ext| async move {
@@ -380,43 +552,145 @@ impl AssetHubClient {
             .collect::<Vec<_>>()
             .await;
 
-        let transfers = events
+        let build_transfer = |asset_id: u32, amount: u128, from, to, index: u32| {
+            let asset_info = assets.get(&asset_id)?;
+
+            Some(ChainTransfer {
+                asset_id,
+                asset_name: asset_info.name.clone(),
+                // TODO: check event.amount? Cast is quite unsafe
+                #[expect(clippy::cast_possible_truncation)]
+                amount: Decimal::new(
+                    amount as i64,
+                    asset_info.decimals.into(),
+                ),
+                sender: from,
+                recipient: to,
+                transaction_id: (block_number, index),
+                timestamp,
+            })
+        };
+
+        let mut transfers: Vec<_> = events
             .into_iter()
             .flat_map(|(index, events)| {
                 events
                     .find::<TransferredEvent>()
                     .filter_map(Result::ok)
                     .filter_map(|event| {
-                        let asset_info = assets.get(&event.asset_id)?;
-
-                        Some(ChainTransfer {
-                            asset_id: event.asset_id,
-                            asset_name: asset_info.name.clone(),
-                            // TODO: check event.amount? Cast is quite unsafe
-                            #[expect(clippy::cast_possible_truncation)]
-                            amount: Decimal::new(
-                                event.amount as i64,
-                                asset_info.decimals.into(),
-                            ),
-                            sender: event.from,
-                            recipient: event.to,
-                            transaction_id: (block_number, index),
-                            timestamp,
-                        })
+                        build_transfer(
+                            event.asset_id,
+                            event.amount,
+                            event.from,
+                            event.to,
+                            index,
+                        )
                     })
                     .collect::<Vec<_>>()
             })
             .collect();
 
+        // Catch transfers emitted by extrinsics we don't specifically decode
+        // (e.g. a `Transferred` event nested inside a `utility.batchAll`),
+        // which the extrinsic-scoped scan above misses entirely.
+        //
+        // TODO: if an unrecognized extrinsic emits more than one `Transferred`
+        // event, they'll collide on `transaction_id`, since it only tracks
+        // the extrinsic index, not the event's position within it (same
+        // limitation already tracked for Polygon's `TransactionId`).
+        if let Ok(block_events) = block.events().await {
+            for event_details in block_events
+                .iter()
+                .filter_map(Result::ok)
+            {
+                let Phase::ApplyExtrinsic(extrinsic_index) = event_details.phase() else {
+                    continue;
+                };
+
+                if known_extrinsic_indices.contains(&extrinsic_index) {
+                    continue;
+                }
+
+                let Ok(Some(event)) = event_details.as_event::<TransferredEvent>() else {
+                    continue;
+                };
+
+                if let Some(transfer) = build_transfer(
+                    event.asset_id,
+                    event.amount,
+                    event.from,
+                    event.to,
+                    extrinsic_index,
+                ) {
+                    transfers.push(transfer);
+                }
+            }
+        }
+
         Ok(transfers)
     }
 
-    #[expect(clippy::unused_self)]
-    fn build_tx_config(
+    /// Fallback for [`crate::configs::BlockSource::Polling`]: periodically ask
+    /// for the latest finalized block instead of subscribing for pushes.
+    /// Intended for nodes/providers that don't support
+    /// `chain_subscribeFinalizedHeads`. Only ever processes the single latest
+    /// polled block — if `BLOCK_POLL_INTERVAL` is long enough for more than
+    /// one block to land between polls, the earlier ones are skipped rather
+    /// than backfilled, so this trades completeness for simplicity. Use the
+    /// default subscription-based mode unless the endpoint actually requires
+    /// this.
+    fn poll_finalized_blocks(
+        self,
+        assets: HashMap<u32, AssetInfo<AssetHubChainConfig>>,
+    ) -> TransfersStream<AssetHubChainConfig> {
+        let client = self;
+
+        let stream = async_stream::try_stream! {
+            let mut last_processed_block = None;
+
+            loop {
+                tokio::time::sleep(BLOCK_POLL_INTERVAL).await;
+
+                let block = client
+                    .client
+                    .blocks()
+                    .at_latest()
+                    .await
+                    .inspect_err(|e| {
+                        tracing::debug!(
+                            error.category = crate::utils::logging::category::CHAIN_CLIENT,
+                            error.operation = crate::utils::logging::operation::SUBSCRIBE_TRANSFERS,
+                            error.source = ?e,
+                            "Failed to poll for latest finalized block"
+                        );
+                    })
+                    .map_err(|_e| SubscriptionError::StreamClosed)?;
+
+                if last_processed_block == Some(block.number()) {
+                    continue;
+                }
+
+                last_processed_block = Some(block.number());
+
+                let result = client.process_block(block, &assets).await?;
+
+                if !result.is_empty() {
+                    yield result
+                }
+            }
+        };
+
+        Box::pin(stream)
+    }
+
+    async fn build_tx_config(
         &self,
         asset_id: u32,
-    ) -> <DefaultExtrinsicParams<SubxtAssetHubConfig> as ExtrinsicParams<SubxtAssetHubConfig>>::Params
-    {
+        sender: &AssetHubAccountId,
+    ) -> Result<
+        <DefaultExtrinsicParams<SubxtAssetHubConfig> as ExtrinsicParams<SubxtAssetHubConfig>>::Params,
+        TransactionError<AssetHubChainConfig>,
+    >{
         let location = MultiLocation {
             parents: DEFAULT_MULTILOCATION_PARENTS,
             interior: Junctions::X2(
@@ -425,10 +699,165 @@ impl AssetHubClient {
             ),
         };
 
-        DefaultExtrinsicParamsBuilder::<SubxtAssetHubConfig>::new()
-            .tip_of(0, location)
-            .mortal(DEFAULT_MORTALITY)
-            .build()
+        let nonce = self
+            .nonce_tracker
+            .next_nonce(sender, &self.client)
+            .await
+            .map_err(|e| {
+                tracing::debug!(
+                    error.category = crate::utils::logging::category::CHAIN_CLIENT,
+                    error.operation = crate::utils::logging::operation::BUILD_TRANSFER,
+                    error.source = ?e,
+                    "Failed to fetch account nonce"
+                );
+                TransactionError::BuildFailed {
+                    reason: "Failed to fetch account nonce".to_string(),
+                }
+            })?;
+
+        Ok(
+            DefaultExtrinsicParamsBuilder::<SubxtAssetHubConfig>::new()
+                .tip_of(0, location)
+                .mortal(DEFAULT_MORTALITY)
+                .nonce(nonce)
+                .build(),
+        )
+    }
+
+    /// Fetch the `Assets::Account` balance for many accounts in one round
+    /// trip via the node's `state_queryStorageAt` batch RPC, instead of one
+    /// [`Self::fetch_asset_balance`] call per account. Cuts latency
+    /// proportionally for reconciliation tooling rechecking many
+    /// treasury/watched addresses at once (see
+    /// `BalanceChecker::get_asset_hub_account_balances`). Output order
+    /// matches `accounts`; an account with no storage entry for `asset_id`
+    /// reports a zero balance, matching [`Self::fetch_asset_balance`].
+    #[instrument(skip(self, accounts), fields(asset_id = %asset_id, accounts = accounts.len()))]
+    pub(crate) async fn balances_at_accounts(
+        &self,
+        asset_id: u32,
+        accounts: &[AssetHubAccountId],
+    ) -> Result<Vec<Decimal>, QueryError> {
+        if accounts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let decimals = self
+            .asset_info_store
+            .get_asset_info(&asset_id)
+            .await
+            .ok_or_else(|| QueryError::NotFound {
+                query_type: format!("asset info for asset {asset_id}"),
+            })?
+            .decimals;
+
+        let metadata = self.client.metadata();
+
+        let value_type_id = subxt::ext::subxt_core::storage::lookup_storage_entry_details(
+            "Assets", "Account", &metadata,
+        )
+        .map_err(|e| QueryError::MetadataDecode {
+            pallet: "Assets".to_string(),
+            item: "Account".to_string(),
+            source: e.to_string(),
+        })?
+        .1
+        .entry_type()
+        .value_ty();
+
+        let keys = accounts
+            .iter()
+            .map(|account| {
+                let address = runtime::storage()
+                    .assets()
+                    .account(asset_id, account.clone());
+                let mut bytes = address.to_root_bytes();
+                address
+                    .append_entry_bytes(&metadata, &mut bytes)
+                    .map_err(|e| QueryError::MetadataDecode {
+                        pallet: "Assets".to_string(),
+                        item: "Account".to_string(),
+                        source: e.to_string(),
+                    })?;
+                Ok(bytes)
+            })
+            .collect::<Result<Vec<Vec<u8>>, QueryError>>()?;
+
+        let rpc_client = if self.config.allow_insecure_endpoints {
+            subxt::backend::rpc::RpcClient::from_insecure_url(&self.connected_endpoint).await
+        } else {
+            subxt::backend::rpc::RpcClient::from_url(&self.connected_endpoint).await
+        }
+        .map_err(|e| {
+            tracing::debug!(
+                error.category = crate::utils::logging::category::CHAIN_CLIENT,
+                error.source = ?e,
+                endpoint = %self.connected_endpoint,
+                "Failed to open RPC connection for batched balance lookup"
+            );
+            QueryError::RpcRequestFailed {
+                endpoint: self.connected_endpoint.clone(),
+            }
+        })?;
+
+        let change_sets =
+            subxt::backend::legacy::LegacyRpcMethods::<SubxtAssetHubConfig>::new(rpc_client)
+                .state_query_storage_at(keys.iter().map(Vec::as_slice), None)
+                .await
+                .inspect_err(|e| {
+                    tracing::debug!(
+                        error.category = crate::utils::logging::category::CHAIN_CLIENT,
+                        error.source = ?e,
+                        asset_id = %asset_id,
+                        "Failed to batch-query storage for balances"
+                    );
+                })
+                .map_err(|_e| QueryError::RpcRequestFailed {
+                    endpoint: self.connected_endpoint.clone(),
+                })?;
+
+        let mut raw_values: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+        for change_set in change_sets {
+            for (key, data) in change_set.changes {
+                if let Some(data) = data {
+                    raw_values.insert(key.0, data.0);
+                }
+            }
+        }
+
+        keys.into_iter()
+            .map(|key| match raw_values.get(&key) {
+                None => Ok(Decimal::ZERO),
+                Some(bytes) => {
+                    let value = subxt::dynamic::DecodedValueThunk::decode_with_metadata(
+                        &mut &bytes[..],
+                        value_type_id,
+                        &metadata,
+                    )
+                    .and_then(|thunk| thunk.to_value())
+                    .map_err(|e| QueryError::MetadataDecode {
+                        pallet: "Assets".to_string(),
+                        item: "Account".to_string(),
+                        source: e.to_string(),
+                    })?;
+
+                    let balance = value
+                        .at("balance")
+                        .and_then(subxt::ext::scale_value::Value::as_u128)
+                        .ok_or_else(|| QueryError::MetadataDecode {
+                            pallet: "Assets".to_string(),
+                            item: "Account".to_string(),
+                            source: "decoded value has no `balance` field".to_string(),
+                        })?;
+
+                    #[expect(clippy::cast_possible_truncation)]
+                    Ok(Decimal::new(
+                        balance as i64,
+                        decimals.into(),
+                    ))
+                },
+            })
+            .collect()
     }
 }
 
@@ -446,7 +875,13 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
 
     #[instrument(skip(config))]
     async fn new(config: &crate::configs::ChainConfig) -> Result<Self, ClientError> {
-        AssetHubClient::from_config(config, AssetInfoStore::new()).await
+        AssetHubClient::from_config(
+            config,
+            AssetInfoStore::new(),
+            NonceTracker::new(),
+            ChainTipTracker::new(),
+        )
+        .await
     }
 
     #[instrument(skip(config, asset_info_store))]
@@ -454,7 +889,13 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
         config: &crate::configs::ChainConfig,
         asset_info_store: AssetInfoStore<AssetHubChainConfig>,
     ) -> Result<Self, ClientError> {
-        AssetHubClient::from_config(config, asset_info_store).await
+        AssetHubClient::from_config(
+            config,
+            asset_info_store,
+            NonceTracker::new(),
+            ChainTipTracker::new(),
+        )
+        .await
     }
 
     #[instrument(skip(self))]
@@ -462,6 +903,8 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
         Self::from_config(
             &self.config,
             self.asset_info_store.clone(),
+            self.nonce_tracker.clone(),
+            self.chain_tip.clone(),
         )
         .await
     }
@@ -472,11 +915,9 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
         asset_id: &u32,
     ) -> Result<AssetInfo<AssetHubChainConfig>, QueryError> {
         debug!(message = "Trying to fetch asset info...");
-        let request_data = runtime::storage()
-            .assets()
-            .metadata(*asset_id);
 
-        self.client
+        let storage = self
+            .client
             .storage()
             .at_latest()
             .await
@@ -489,8 +930,16 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
                     "Failed to get latest storage"
                 );
             })
-            .map_err(|_e| QueryError::RpcRequestFailed)?
-            .fetch(&request_data)
+            .map_err(|_e| QueryError::RpcRequestFailed {
+                endpoint: self.connected_endpoint.clone(),
+            })?;
+
+        let metadata = storage
+            .fetch(
+                &runtime::storage()
+                    .assets()
+                    .metadata(*asset_id),
+            )
             .await
             .inspect_err(|e| {
                 tracing::debug!(
@@ -501,25 +950,85 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
                     "Failed to fetch asset metadata from storage"
                 );
             })
-            .map_err(|_e| QueryError::RpcRequestFailed)?
+            .map_err(|e| {
+                if is_metadata_decode_error(&e) {
+                    QueryError::MetadataDecode {
+                        pallet: "Assets".to_string(),
+                        item: "Metadata".to_string(),
+                        source: e.to_string(),
+                    }
+                } else {
+                    QueryError::RpcRequestFailed {
+                        endpoint: self.connected_endpoint.clone(),
+                    }
+                }
+            })?
             .ok_or_else(|| QueryError::NotFound {
                 query_type: format!("asset metadata for asset {asset_id}"),
             })
-            .inspect_err(|_| warn!(message = "Asset metadata wasn't found (None returned)"))
-            .map(|resp| AssetInfo {
-                id: *asset_id,
-                name: String::from_utf8(resp.symbol.0)
-                    .inspect_err(|e| {
-                        tracing::warn!(
-                            asset_id = %asset_id,
-                            error = ?e,
-                            "Asset symbol contains invalid UTF-8, using fallback"
-                        );
-                    })
-                    .unwrap_or_else(|_| format!("Asset_{asset_id}")),
-                decimals: resp.decimals,
+            .inspect_err(|_| warn!(message = "Asset metadata wasn't found (None returned)"))?;
+
+        // `Assets::Asset` carries the asset's own minimum balance, separate
+        // from `Assets::Metadata`, so it needs its own storage fetch.
+        let details = storage
+            .fetch(
+                &runtime::storage()
+                    .assets()
+                    .asset(*asset_id),
+            )
+            .await
+            .inspect_err(|e| {
+                tracing::debug!(
+                    error.category = crate::utils::logging::category::CHAIN_CLIENT,
+                    error.operation = crate::utils::logging::operation::FETCH_ASSET_INFO,
+                    error.source = ?e,
+                    asset_id = %asset_id,
+                    "Failed to fetch asset details from storage"
+                );
+            })
+            .map_err(|e| {
+                if is_metadata_decode_error(&e) {
+                    QueryError::MetadataDecode {
+                        pallet: "Assets".to_string(),
+                        item: "Asset".to_string(),
+                        source: e.to_string(),
+                    }
+                } else {
+                    QueryError::RpcRequestFailed {
+                        endpoint: self.connected_endpoint.clone(),
+                    }
+                }
+            })?
+            .ok_or_else(|| QueryError::NotFound {
+                query_type: format!("asset details for asset {asset_id}"),
             })
-            .inspect(|val| debug!(message = "Asset info fetched successfully", asset_info = ?val))
+            .inspect_err(|_| warn!(message = "Asset details weren't found (None returned)"))?;
+
+        // TODO: check details.min_balance? Cast is quite unsafe
+        #[expect(clippy::cast_possible_truncation)]
+        let min_balance = Decimal::new(
+            details.min_balance as i64,
+            metadata.decimals.into(),
+        );
+
+        let asset_info = AssetInfo {
+            id: *asset_id,
+            name: String::from_utf8(metadata.symbol.0)
+                .inspect_err(|e| {
+                    tracing::warn!(
+                        asset_id = %asset_id,
+                        error = ?e,
+                        "Asset symbol contains invalid UTF-8, using fallback"
+                    );
+                })
+                .unwrap_or_else(|_| format!("Asset_{asset_id}")),
+            decimals: metadata.decimals,
+            min_balance,
+        };
+
+        debug!(message = "Asset info fetched successfully", asset_info = ?asset_info);
+
+        Ok(asset_info)
     }
 
     // TODO: probably will be better to return some `Balance` structure with asset
@@ -564,7 +1073,9 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
                     "Failed to get latest storage"
                 );
             })
-            .map_err(|_e| QueryError::RpcRequestFailed)?
+            .map_err(|_e| QueryError::RpcRequestFailed {
+                endpoint: self.connected_endpoint.clone(),
+            })?
             .fetch(&request_data)
             .await
             .inspect_err(|e| {
@@ -577,7 +1088,19 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
                     "Failed to fetch balance from storage"
                 );
             })
-            .map_err(|_e| QueryError::RpcRequestFailed)?
+            .map_err(|e| {
+                if is_metadata_decode_error(&e) {
+                    QueryError::MetadataDecode {
+                        pallet: "Assets".to_string(),
+                        item: "Account".to_string(),
+                        source: e.to_string(),
+                    }
+                } else {
+                    QueryError::RpcRequestFailed {
+                        endpoint: self.connected_endpoint.clone(),
+                    }
+                }
+            })?
             .map_or(Decimal::ZERO, |acc| {
                 // TODO: check acc.balance? Cast is quite unsafe
                 #[expect(clippy::cast_possible_truncation)]
@@ -607,6 +1130,10 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
             }
         }
 
+        if client.config.block_source == crate::configs::BlockSource::Polling {
+            return Ok(client.poll_finalized_blocks(assets));
+        }
+
         // Subscribe to finalized blocks
         let mut blocks = client
             .client
@@ -660,6 +1187,16 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
         BlockChainClientExt::init_asset_info_impl(self, asset_ids).await
     }
 
+    async fn chain_tip(&self) -> Option<ChainTip> {
+        self.chain_tip.get().await
+    }
+
+    async fn block_time_estimate_millis(&self) -> Option<u64> {
+        self.chain_tip
+            .block_time_estimate_millis()
+            .await
+    }
+
     #[instrument(skip(self), fields(asset_id = %asset_id, amount = %amount))]
     async fn build_transfer(
         &self,
@@ -696,7 +1233,9 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
                 }
             })?;
 
-        let tx_config = self.build_tx_config(asset_id);
+        let tx_config = self
+            .build_tx_config(asset_id, &sender)
+            .await?;
 
         let call = runtime::tx().assets().transfer(
             asset_id,
@@ -729,22 +1268,27 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
         })
     }
 
-    #[instrument(skip(self), fields(asset_id = %asset_id))]
+    #[instrument(skip(self), fields(asset_id = %asset_id, mode = ?mode))]
     async fn build_transfer_all(
         &self,
         sender: AssetHubAccountId,
         recipient: AssetHubAccountId,
         asset_id: u32,
+        mode: SweepMode,
     ) -> Result<UnsignedTransaction<AssetHubChainConfig>, TransactionError<AssetHubChainConfig>>
     {
         // TODO: in order to support native asset, we need to check if asset_id = 0 and
         // use other methods to build transfer_all for native asset
-        let tx_config = self.build_tx_config(asset_id);
+        let tx_config = self
+            .build_tx_config(asset_id, &sender)
+            .await?;
+
+        let keep_alive = mode == SweepMode::KeepAlive;
 
         let call = runtime::tx().assets().transfer_all(
             asset_id,
             recipient.clone().into(),
-            false,
+            keep_alive,
         );
 
         let transaction = self
@@ -798,6 +1342,7 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
     async fn submit_and_watch_transaction(
         &self,
         transaction: SignedTransaction<AssetHubChainConfig>,
+        sender: AssetHubAccountId,
     ) -> Result<ChainTransfer<AssetHubChainConfig>, TransactionError<AssetHubChainConfig>> {
         let SignedTransaction {
             transaction,
@@ -806,19 +1351,28 @@ impl BlockChainClient<AssetHubChainConfig> for AssetHubClient {
         let tx_hash = transaction.hash();
 
         // Submit the transaction and wait for it's finalization
-        let tx_progress = transaction
-            .submit_and_watch()
-            .await
-            .inspect_err(|e| {
-                tracing::debug!(
-                    error.category = crate::utils::logging::category::CHAIN_CLIENT,
-                    error.operation = crate::utils::logging::operation::SUBMIT_TRANSACTION,
-                    error.source = ?e,
-                    transaction_hash = %tx_hash,
-                    "Transaction submission failed"
-                );
-            })
-            .map_err(|_| TransactionError::SubmissionStatusUnknown)?;
+        let submission_result = transaction.submit_and_watch().await;
+
+        if let Err(e) = &submission_result {
+            tracing::debug!(
+                error.category = crate::utils::logging::category::CHAIN_CLIENT,
+                error.operation = crate::utils::logging::operation::SUBMIT_TRANSACTION,
+                error.source = ?e,
+                transaction_hash = %tx_hash,
+                "Transaction submission failed"
+            );
+
+            // The pool rejects submissions with a nonce it already has
+            // (stale) or one too far ahead (future); either means our local
+            // cache has drifted from the chain's view, so drop it and let
+            // the next extrinsic re-fetch.
+            if is_stale_or_future_nonce_error(e) {
+                self.nonce_tracker.resync(&sender).await;
+            }
+        }
+
+        let tx_progress =
+            submission_result.map_err(|_| TransactionError::SubmissionStatusUnknown)?;
 
         // Wait for tx finalization. We don't really know neither it's status or block
         // info at this point
@@ -31,6 +31,16 @@ pub enum ClientError {
     /// Principle 1)
     #[error("Unknown asset ID in configuration: {asset_id}")]
     UnknownAssetId { asset_id: u32 },
+
+    #[expect(dead_code)]
+    /// The endpoint's TLS certificate doesn't match its configured pinned
+    /// fingerprint (`ChainConfig::fingerprint_for`). Not yet raised anywhere:
+    /// enforcing it requires a custom certificate verifier plugged into the
+    /// WS transport, which neither subxt's `from_url`/`from_insecure_url`
+    /// nor alloy's `WsConnect` expose a hook for today (see the TODO at each
+    /// connection loop in `asset_hub.rs`/`polygon.rs`).
+    #[error("TLS certificate for endpoint {endpoint} does not match its configured fingerprint")]
+    CertificateMismatch { endpoint: String },
 }
 
 // ============================================================================
@@ -41,17 +51,25 @@ pub enum ClientError {
 #[derive(Debug, Error)]
 pub enum QueryError {
     /// RPC request failed - triggers endpoint failover
-    #[error("RPC request failed")]
-    RpcRequestFailed,
+    #[error("RPC request to endpoint {endpoint} failed")]
+    RpcRequestFailed { endpoint: String },
 
     /// Storage query returned no data
     #[error("Storage query returned no data: {query_type}")]
     NotFound { query_type: String },
 
-    #[expect(dead_code)]
-    /// Data decoding failed (SCALE or other format)
-    #[error("Data decoding failed: {data_type}")]
-    DecodeFailed { data_type: String },
+    /// Storage item couldn't be located or decoded against the connected
+    /// runtime's metadata, most likely because a runtime upgrade changed or
+    /// removed the storage item's shape. Distinct from
+    /// [`QueryError::RpcRequestFailed`] so operators can tell "the chain
+    /// didn't answer" apart from "the chain answered with something our
+    /// metadata no longer understands".
+    #[error("Failed to decode storage item {pallet}::{item}: {source}")]
+    MetadataDecode {
+        pallet: String,
+        item: String,
+        source: String,
+    },
 
     #[expect(dead_code)]
     #[error("Invalid query params")]
@@ -173,3 +191,30 @@ pub fn is_insufficient_balance_error<T: std::fmt::Debug>(error: &T) -> bool {
         || error_details.contains("InsufficientBalance")
         || error_details.contains("BalanceTooLow")
 }
+
+/// Check if a transaction submission error indicates a stale or future nonce
+///
+/// A local nonce cache (see `NonceTracker` in `chain_client/asset_hub.rs`)
+/// can drift from the chain's view of an account's nonce, e.g. if a prior
+/// submission was dropped from the pool. Such errors mean the cache should
+/// be discarded so the next extrinsic re-fetches the nonce from chain.
+///
+/// Note: Generic over any type that implements Debug to support different
+/// runtime error types
+pub fn is_stale_or_future_nonce_error<T: std::fmt::Debug>(error: &T) -> bool {
+    let error_details = format!("{error:?}");
+    error_details.contains("Stale") || error_details.contains("Future")
+}
+
+/// Check if a `subxt` storage query error indicates a metadata mismatch
+/// (storage item not found, or couldn't be decoded) rather than an RPC
+/// transport failure. Unlike the other helpers here, this matches on the
+/// concrete `subxt::Error` variants directly instead of string-matching a
+/// `Debug` format, since `subxt` exposes distinct `Metadata`/`Decode`
+/// variants for exactly this case.
+pub fn is_metadata_decode_error(error: &subxt::Error) -> bool {
+    matches!(
+        error,
+        subxt::Error::Metadata(_) | subxt::Error::Decode(_)
+    )
+}
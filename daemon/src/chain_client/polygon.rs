@@ -6,6 +6,7 @@
 mod consts;
 mod pimlico_client;
 
+use std::future::Future;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -53,6 +54,8 @@ use super::{
     BlockChainClient,
     BlockChainClientExt,
     ChainConfig,
+    ChainTip,
+    ChainTipTracker,
     ChainTransfer,
     ClientError,
     GeneralTransactionId,
@@ -62,6 +65,7 @@ use super::{
     SignedTransaction,
     SignedTransactionUtils,
     SubscriptionError,
+    SweepMode,
     TransactionError,
     TransfersStream,
     UnsignedTransaction,
@@ -85,6 +89,35 @@ use pimlico_client::{
 };
 
 const WS_MESSAGES_TIMEOUT_DURATION: Duration = Duration::from_secs(10);
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Poll `current_block_number` until it reports a block at or above `target`.
+/// Pulled out of `wait_for_confirmations` so the reorg-wait loop itself can be
+/// unit-tested against a fake block-number source instead of a live
+/// `subscription_provider`.
+async fn wait_for_block_height<F, Fut, E>(
+    target: u64,
+    mut current_block_number: F,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<u64, E>>,
+    E: std::fmt::Debug,
+{
+    loop {
+        match current_block_number().await {
+            Ok(head) if head >= target => return,
+            Ok(_) => {},
+            Err(e) => {
+                tracing::warn!(
+                    error = ?e,
+                    "Failed to fetch current block number while waiting for confirmations"
+                );
+            },
+        }
+
+        tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+    }
+}
 
 // ============================================================================
 // ERC-20 Interface Definition
@@ -283,21 +316,25 @@ fn u256_to_decimal(
     raw_decimal * scale
 }
 
-/// Convert a Decimal to U256 with the given number of decimals
+/// Convert a Decimal to U256 with the given number of decimals. Returns
+/// `None` if `value` is negative, has more fractional digits than `decimals`
+/// allows (it would be truncated), or doesn't fit in a `u128` once scaled up
+/// to base units. All amounts reaching this conversion originate on-chain
+/// (received balances, prior transfers) and are already scaled to the
+/// asset's exact decimals, so rejecting excess precision is a bug signal,
+/// not a case callers need to round past.
 fn decimal_to_u256(
     value: Decimal,
     decimals: u8,
-) -> U256 {
-    // Scale up by decimals
+) -> Option<U256> {
+    if value.is_sign_negative() || value.scale() > u32::from(decimals) {
+        return None;
+    }
+
     let multiplier = Decimal::new(10_i64.pow(u32::from(decimals)), 0);
-    #[expect(clippy::arithmetic_side_effects)]
-    let scaled = value * multiplier;
-
-    // Convert to U256
-    scaled
-        .to_u128()
-        .map(U256::from)
-        .unwrap_or(U256::ZERO)
+    let scaled = value.checked_mul(multiplier)?;
+
+    scaled.to_u128().map(U256::from)
 }
 
 pub(super) fn pack_u128_to_bytes(
@@ -324,49 +361,72 @@ pub struct PolygonClient {
     provider: PolygonProvider,
     subscription_provider: PolygonProvider,
     pimlico_client: PimlicoClient,
+    connected_requests_endpoint: String,
+    connected_subscriptions_endpoint: String,
+    chain_tip: ChainTipTracker,
 }
 
 impl PolygonClient {
     /// Create a new Polygon client from configuration
-    #[instrument(skip(config, asset_info_store))]
+    #[instrument(skip(config, asset_info_store, chain_tip))]
     async fn from_config(
         config: &crate::configs::ChainConfig,
         asset_info_store: AssetInfoStore<PolygonChainConfig>,
+        chain_tip: ChainTipTracker,
     ) -> Result<Self, ClientError> {
-        let endpoint = config
-            .get_random_requests_endpoint()
-            .ok_or(ClientError::InvalidConfiguration {
-                field: "endpoints".to_string(),
-            })?;
+        let requests_endpoints = config.shuffled_requests_endpoints();
 
-        tracing::debug!(
-            url = endpoint,
-            chain = %Self::chain_type(),
-            "Trying to connect to endpoint...",
-        );
+        if requests_endpoints.is_empty() {
+            return Err(ClientError::InvalidConfiguration {
+                field: "endpoints".to_string(),
+            });
+        }
 
-        // Test connection and get chain ID
-        let ws_connect = WsConnect::new(&endpoint);
-        let provider = ProviderBuilder::new()
-            .connect_ws(ws_connect)
-            .await
-            .inspect_err(|e| {
-                tracing::debug!(
-                    error.category = CHAIN_CLIENT,
-                    error.operation = "connect_client",
-                    error.source = ?e,
-                    endpoint = %endpoint,
-                    chain = %Self::chain_type(),
-                    "Failed to connect to Polygon RPC endpoint"
-                );
-            })
-            .map_err(|_| ClientError::AllEndpointsUnreachable)?;
+        let mut provider = None;
+
+        for endpoint in &requests_endpoints {
+            tracing::debug!(
+                url = endpoint,
+                chain = %Self::chain_type(),
+                "Trying to connect to endpoint...",
+            );
+
+            // TODO: if `config.fingerprint_for(endpoint)` is set, verify the
+            // endpoint's certificate against it and fail with
+            // `ClientError::CertificateMismatch` rather than connecting.
+            // `WsConnect` doesn't expose a hook into its TLS verifier, so
+            // this needs a hand-built transport with a custom `rustls`
+            // certificate verifier before it can be enforced.
+            // Test connection and get chain ID
+            let ws_connect = WsConnect::new(endpoint);
+            match ProviderBuilder::new()
+                .connect_ws(ws_connect)
+                .await
+            {
+                Ok(connected_provider) => {
+                    tracing::debug!(
+                        url = endpoint,
+                        chain = %Self::chain_type(),
+                        "Connection successful"
+                    );
+                    provider = Some((endpoint.clone(), connected_provider));
+                    break;
+                },
+                Err(e) => {
+                    tracing::debug!(
+                        error.category = CHAIN_CLIENT,
+                        error.operation = "connect_client",
+                        error.source = ?e,
+                        endpoint = %endpoint,
+                        chain = %Self::chain_type(),
+                        "Failed to connect to Polygon RPC endpoint, trying next one"
+                    );
+                },
+            }
+        }
 
-        tracing::debug!(
-            url = endpoint,
-            chain = %Self::chain_type(),
-            "Connection successful"
-        );
+        let (connected_requests_endpoint, provider) =
+            provider.ok_or(ClientError::AllEndpointsUnreachable)?;
 
         // Get chain ID for transaction signing
         let chain_id = provider
@@ -381,32 +441,49 @@ impl PolygonClient {
             })
             .map_err(|_| ClientError::MetadataFetchFailed)?;
 
-        let endpoint = config
-            .get_random_subscriptions_endpoint()
-            .ok_or(ClientError::InvalidConfiguration {
+        let subscriptions_endpoints = config.shuffled_subscriptions_endpoints();
+
+        if subscriptions_endpoints.is_empty() {
+            return Err(ClientError::InvalidConfiguration {
                 field: "endpoints".to_string(),
-            })?;
+            });
+        }
 
-        // Test connection and get chain ID
-        let ws_connect = WsConnect::new(&endpoint);
-        let subscription_provider = ProviderBuilder::new()
-            .connect_ws(ws_connect)
-            .await
-            .inspect_err(|e| {
-                tracing::debug!(
-                    error.category = CHAIN_CLIENT,
-                    error.operation = "connect_client",
-                    error.source = ?e,
-                    endpoint = %endpoint,
-                    chain = %Self::chain_type(),
-                    "Failed to connect to Polygon RPC endpoint"
-                );
-            })
-            .map_err(|_| ClientError::AllEndpointsUnreachable)?;
+        let mut subscription_provider = None;
+        let mut connected_endpoint = None;
+
+        for endpoint in &subscriptions_endpoints {
+            let ws_connect = WsConnect::new(endpoint);
+            match ProviderBuilder::new()
+                .connect_ws(ws_connect)
+                .await
+            {
+                Ok(connected_provider) => {
+                    subscription_provider = Some(connected_provider);
+                    connected_endpoint = Some(endpoint.clone());
+                    break;
+                },
+                Err(e) => {
+                    tracing::debug!(
+                        error.category = CHAIN_CLIENT,
+                        error.operation = "connect_client",
+                        error.source = ?e,
+                        endpoint = %endpoint,
+                        chain = %Self::chain_type(),
+                        "Failed to connect to Polygon RPC endpoint, trying next one"
+                    );
+                },
+            }
+        }
+
+        let subscription_provider =
+            subscription_provider.ok_or(ClientError::AllEndpointsUnreachable)?;
+        let connected_subscriptions_endpoint =
+            connected_endpoint.ok_or(ClientError::AllEndpointsUnreachable)?;
 
         tracing::info!(
             chain_id = chain_id,
-            endpoint = %endpoint,
+            endpoint = %connected_subscriptions_endpoint,
             "Connected to Polygon network"
         );
 
@@ -416,9 +493,55 @@ impl PolygonClient {
             provider,
             subscription_provider,
             pimlico_client: PimlicoClient::new(),
+            connected_requests_endpoint,
+            connected_subscriptions_endpoint,
+            chain_tip,
         })
     }
 
+    /// The RPC endpoint used for requests, for operators monitoring which
+    /// endpoint is in use.
+    pub fn connected_requests_endpoint(&self) -> &str {
+        &self.connected_requests_endpoint
+    }
+
+    /// The RPC endpoint used for subscriptions, for operators monitoring
+    /// which endpoint is in use.
+    pub fn connected_subscriptions_endpoint(&self) -> &str {
+        &self.connected_subscriptions_endpoint
+    }
+
+    /// Wait until `log`'s block is buried under `asset_id`'s confirmation
+    /// depth (see `ChainConfig::confirmation_overrides`) worth of further
+    /// blocks, so a transfer from a block that later gets reorged away isn't
+    /// reported as final. A no-op when that depth is 0 (the default) or the
+    /// log doesn't carry a block number.
+    async fn wait_for_confirmations(
+        &self,
+        log: &Log,
+        asset_id: Address,
+    ) {
+        let confirmations = self
+            .config
+            .confirmations_for(&asset_id.to_string());
+
+        if confirmations == 0 {
+            return;
+        }
+
+        let Some(block_number) = log.block_number else {
+            return;
+        };
+
+        let target = block_number.saturating_add(confirmations);
+
+        wait_for_block_height(target, || {
+            self.subscription_provider
+                .get_block_number()
+        })
+        .await;
+    }
+
     /// Convert a log entry to a ChainTransfer
     async fn log_to_transfer(
         &self,
@@ -668,7 +791,12 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
 
     #[instrument(skip(config))]
     async fn new(config: &crate::configs::ChainConfig) -> Result<Self, ClientError> {
-        Self::from_config(config, AssetInfoStore::new()).await
+        Self::from_config(
+            config,
+            AssetInfoStore::new(),
+            ChainTipTracker::new(),
+        )
+        .await
     }
 
     #[instrument(skip(config, asset_info_store))]
@@ -676,7 +804,12 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
         config: &crate::configs::ChainConfig,
         asset_info_store: AssetInfoStore<PolygonChainConfig>,
     ) -> Result<Self, ClientError> {
-        Self::from_config(config, asset_info_store).await
+        Self::from_config(
+            config,
+            asset_info_store,
+            ChainTipTracker::new(),
+        )
+        .await
     }
 
     #[instrument(skip(self))]
@@ -686,6 +819,7 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
         Self::from_config(
             &self.config,
             self.asset_info_store.clone(),
+            self.chain_tip.clone(),
         )
         .await
     }
@@ -712,7 +846,9 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
                     "Failed to fetch token symbol"
                 );
             })
-            .map_err(|_| QueryError::RpcRequestFailed)?;
+            .map_err(|_| QueryError::RpcRequestFailed {
+                endpoint: self.connected_requests_endpoint.clone(),
+            })?;
 
         // Fetch decimals
         let decimals = contract
@@ -728,12 +864,16 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
                     "Failed to fetch token decimals"
                 );
             })
-            .map_err(|_| QueryError::RpcRequestFailed)?;
+            .map_err(|_| QueryError::RpcRequestFailed {
+                endpoint: self.connected_requests_endpoint.clone(),
+            })?;
 
         let info = AssetInfo {
             id: *asset_id,
             name: symbol,
             decimals,
+            // ERC-20 tokens on Polygon have no minimum-balance/dusting rule.
+            min_balance: Decimal::ZERO,
         };
 
         tracing::trace!(asset_info = ?info, "Asset info fetched successfully");
@@ -777,7 +917,9 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
                     "Failed to fetch token balance"
                 );
             })
-            .map_err(|_| QueryError::RpcRequestFailed)?;
+            .map_err(|_| QueryError::RpcRequestFailed {
+                endpoint: self.connected_requests_endpoint.clone(),
+            })?;
 
         // alloy 1.4 returns the value directly
         let balance = balance_result;
@@ -849,10 +991,54 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
                             break
                         };
 
+                        if log.removed {
+                            // The node detected a reorg and is telling us this
+                            // log's block is no longer on the canonical chain.
+                            // Drop it instead of reporting a transfer that
+                            // never really landed; `wait_for_confirmations`
+                            // keeps this rare in practice, but a node can
+                            // still emit `removed` logs for blocks shallower
+                            // than the configured confirmation depth.
+                            tracing::warn!(
+                                block_hash = ?log.block_hash,
+                                block_number = ?log.block_number,
+                                transaction_hash = ?log.transaction_hash,
+                                "Dropping a Transfer log removed by a chain reorg"
+                            );
+                            continue;
+                        }
+
                         // Decode Transfer event from log
                         match log.log_decode::<IERC20::Transfer>() {
                             Ok(decoded) => {
+                                // Record what the watcher has actually ingested so
+                                // far, independent of whether this log turns into a
+                                // transfer we care about.
+                                if let Some(block_number) = log.block_number {
+                                    client
+                                        .chain_tip
+                                        .set(ChainTip {
+                                            #[expect(clippy::cast_possible_truncation)]
+                                            block_number: block_number as u32,
+                                            block_hash: log
+                                                .block_hash
+                                                .map(|hash| hash.to_string())
+                                                .unwrap_or_default(),
+                                            // We could fetch the block for its real
+                                            // timestamp, but that's an extra RPC
+                                            // round trip per event; `log_to_transfer`
+                                            // makes the same tradeoff below.
+                                            #[expect(clippy::cast_sign_loss)]
+                                            timestamp: chrono::Utc::now().timestamp_millis()
+                                                as u64,
+                                        })
+                                        .await;
+                                }
+
                                 let event = decoded.inner.data;
+                                client
+                                    .wait_for_confirmations(&log, log.address())
+                                    .await;
                                 match client.log_to_transfer(&log, &event).await {
                                     Ok(transfer) => {
                                         tracing::trace!(
@@ -895,6 +1081,16 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
         Ok(Box::pin(stream))
     }
 
+    async fn chain_tip(&self) -> Option<ChainTip> {
+        self.chain_tip.get().await
+    }
+
+    async fn block_time_estimate_millis(&self) -> Option<u64> {
+        self.chain_tip
+            .block_time_estimate_millis()
+            .await
+    }
+
     #[instrument(skip(self))]
     async fn init_asset_info(
         &self,
@@ -920,7 +1116,12 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
             })?
             .decimals;
 
-        let amount_wei = decimal_to_u256(amount, decimals);
+        let amount_wei =
+            decimal_to_u256(amount, decimals).ok_or_else(|| TransactionError::BuildFailed {
+                reason: format!(
+                    "Amount {amount} can't be represented exactly in {decimals} decimals"
+                ),
+            })?;
 
         let contract = IERC20::new(asset_id, self.provider.clone());
         let entrypoint_contract = IERC20::new(ENTRYPOINT, self.provider.clone());
@@ -1025,6 +1226,10 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
         sender: PolygonAccountId,
         recipient: PolygonAccountId,
         asset_id: PolygonAssetId,
+        // Polygon's ERC20-style assets have no existential deposit, so there's
+        // no account-death risk to guard against: the full balance is always
+        // swept regardless of the requested mode.
+        _mode: SweepMode,
     ) -> Result<UnsignedTransaction<PolygonChainConfig>, TransactionError<PolygonChainConfig>> {
         // Fetch current balance
         let balance = self
@@ -1207,6 +1412,10 @@ impl BlockChainClient<PolygonChainConfig> for PolygonClient {
     async fn submit_and_watch_transaction(
         &self,
         transaction: SignedTransaction<PolygonChainConfig>,
+        // Polygon's paymaster-sponsored UserOperations carry their own
+        // entrypoint nonce baked in at build time; there's no local nonce
+        // cache here to resync, so the sender is unused.
+        _sender: PolygonAccountId,
     ) -> Result<ChainTransfer<PolygonChainConfig>, TransactionError<PolygonChainConfig>> {
         let PolygonSignedTransaction {
             op_params,
@@ -1298,6 +1507,81 @@ mod tests {
 
         // Convert back
         let back = decimal_to_u256(decimal, 6);
-        assert_eq!(back, value);
+        assert_eq!(back, Some(value));
+    }
+
+    #[test]
+    fn test_decimal_to_u256_rejects_excess_fractional_digits() {
+        // 6 decimals can't represent a 7th fractional digit exactly
+        let value = Decimal::new(1_234_567_8, 7); // 1.2345678
+        assert_eq!(decimal_to_u256(value, 6), None);
+    }
+
+    #[test]
+    fn test_decimal_to_u256_rejects_negative_amounts() {
+        assert_eq!(
+            decimal_to_u256(Decimal::new(-1, 0), 6),
+            None
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_block_height_returns_once_target_is_reached() {
+        // Fakes a chain that's already buried the target block: no reorg,
+        // no polling needed.
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+
+        wait_for_block_height(100, || {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::future::ready(Ok::<u64, std::convert::Infallible>(100))
+        })
+        .await;
+
+        assert_eq!(
+            calls.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_block_height_polls_until_reorg_is_buried() {
+        // Simulates the chain head advancing by one block per poll, so the
+        // loop has to run a few times before the target is finally buried
+        // deep enough to be reported.
+        let head = std::sync::atomic::AtomicU64::new(97);
+
+        wait_for_block_height(100, || {
+            let current = head.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::future::ready(Ok::<u64, std::convert::Infallible>(
+                current,
+            ))
+        })
+        .await;
+
+        assert!(head.load(std::sync::atomic::Ordering::SeqCst) >= 100);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_for_block_height_retries_after_a_transient_rpc_error() {
+        // A single failed poll (e.g. a dropped RPC connection) shouldn't
+        // abort the wait — it should just retry on the next tick.
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+
+        wait_for_block_height(100, || {
+            let call = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::future::ready(
+                if call == 0 {
+                    Err("transient RPC error")
+                } else {
+                    Ok(100)
+                },
+            )
+        })
+        .await;
+
+        assert_eq!(
+            calls.load(std::sync::atomic::Ordering::SeqCst),
+            2
+        );
     }
 }
@@ -32,6 +32,8 @@ pub const DEFAULT_OVERPAYMENT_TOLERANCE: Decimal = dec!(0.1);
 
 pub const DEFAULT_INVOICE_LIFETIME_MILLIS: u64 = 86_400_000; // 24 hours
 
+pub const DEFAULT_EXPIRATION_CHECK_INTERVAL_MILLIS: u64 = 10_000;
+
 pub const DEFAULT_ALLOW_INSECURE_ENDPOINTS: bool = false;
 
 pub const DEFAULT_CHAIN: ChainType = ChainType::Polygon;
@@ -52,3 +54,22 @@ pub const DEFAULT_LOG_DIRECTIVES: &str = "kalatori=trace,info";
 pub const DEFAULT_ETHERSCAN_LIMIT_PER_SECOND: NonZeroU32 = NonZeroU32::new(3).unwrap();
 
 pub const DEFAULT_AUTH_CLOCK_TOLERANCE_SECS: u64 = 30;
+
+pub const DEFAULT_WEBHOOK_TIMEOUT_SECS: u64 = 60;
+
+pub const DEFAULT_RECENT_EVENTS_BUFFER_SIZE: usize = 256;
+
+pub const DEFAULT_WEBHOOK_MAX_TRANSACTIONS: usize = 100;
+
+pub const DEFAULT_WEBHOOK_MAX_CONCURRENT_REQUESTS: usize = 10;
+
+pub const DEFAULT_WEBHOOK_CONTENT_TYPE: &str = "application/json";
+
+/// After this many failed delivery attempts, a retriable webhook failure
+/// stops being retried and is dead-lettered instead of backing off forever.
+pub const DEFAULT_WEBHOOK_MAX_ATTEMPTS: u32 = 10;
+
+/// Number of additional blocks a transfer must be buried under before it's
+/// reported. 0 preserves the previous behaviour of reporting transfers as
+/// soon as they're seen.
+pub const DEFAULT_CONFIRMATIONS: u64 = 0;
@@ -4,7 +4,6 @@ use std::collections::{
 };
 use std::net::IpAddr;
 use std::num::NonZeroU32;
-use std::str::FromStr;
 
 use kalatori_client::strum::IntoEnumIterator;
 use rand::prelude::*;
@@ -15,7 +14,11 @@ use serde::{
     Serialize,
 };
 
-use crate::chain::utils::to_base58_string;
+use crate::chain::utils::{
+    POLKADOT_SS58_PREFIX,
+    from_base58_string,
+    to_base58_string,
+};
 use crate::types::{
     ChainType,
     DetectedShopPlatform,
@@ -26,8 +29,10 @@ use super::consts::{
     DEFAULT_ASSET_HUB_ASSET_ID,
     DEFAULT_AUTH_CLOCK_TOLERANCE_SECS,
     DEFAULT_CHAIN,
+    DEFAULT_CONFIRMATIONS,
     DEFAULT_DATABASE_DIR,
     DEFAULT_ETHERSCAN_LIMIT_PER_SECOND,
+    DEFAULT_EXPIRATION_CHECK_INTERVAL_MILLIS,
     DEFAULT_HOST,
     DEFAULT_INVOICE_LIFETIME_MILLIS,
     DEFAULT_LOG_DIRECTIVES,
@@ -36,8 +41,14 @@ use super::consts::{
     DEFAULT_POLYGON_ENDPOINTS,
     DEFAULT_POLYGON_USDC_ADDRESS,
     DEFAULT_PORT,
+    DEFAULT_RECENT_EVENTS_BUFFER_SIZE,
     DEFAULT_SIGNATURE_MAX_AGE_SECS,
     DEFAULT_UNDERPAYMENT_TOLERANCE,
+    DEFAULT_WEBHOOK_CONTENT_TYPE,
+    DEFAULT_WEBHOOK_MAX_ATTEMPTS,
+    DEFAULT_WEBHOOK_MAX_CONCURRENT_REQUESTS,
+    DEFAULT_WEBHOOK_MAX_TRANSACTIONS,
+    DEFAULT_WEBHOOK_TIMEOUT_SECS,
 };
 
 #[derive(Deserialize)]
@@ -53,22 +64,152 @@ fn default_allow_insecure_endpoints() -> bool {
     DEFAULT_ALLOW_INSECURE_ENDPOINTS
 }
 
+fn default_confirmations() -> u64 {
+    DEFAULT_CONFIRMATIONS
+}
+
 #[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum EndpointAllowedOperation {
     Subscriptions,
     Requests,
 }
 
+/// A chain RPC endpoint URL, validated to use the `ws://`/`wss://` scheme at
+/// config load time rather than failing on the first connection attempt.
+/// Multiple assets monitored on the same chain share the same `ChainConfig`
+/// (and therefore the same set of `RpcUrl`s), so there's nothing further to
+/// dedupe here.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RpcUrl(String);
+
+impl RpcUrl {
+    fn parse(raw: &str) -> Result<Self, String> {
+        let parsed =
+            url::Url::parse(raw).map_err(|e| format!("Invalid RPC endpoint URL \"{raw}\": {e}"))?;
+
+        match parsed.scheme() {
+            "ws" | "wss" => Ok(RpcUrl(raw.to_string())),
+            other => Err(format!(
+                "RPC endpoint URL \"{raw}\" has unsupported scheme \"{other}\"; only ws:// and wss:// are allowed"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for RpcUrl {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for RpcUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        RpcUrl::parse(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A pinned SHA-256 TLS certificate fingerprint for an RPC endpoint, stored
+/// as lowercase hex. Validated for shape at config load time only; no
+/// connection code compares an endpoint's certificate against it
+/// (`ChainConfig::fingerprint_for` has no callers).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsFingerprint(String);
+
+impl TlsFingerprint {
+    fn parse(raw: &str) -> Result<Self, String> {
+        if raw.len() == 64
+            && raw
+                .bytes()
+                .all(|b| b.is_ascii_hexdigit())
+        {
+            Ok(TlsFingerprint(raw.to_ascii_lowercase()))
+        } else {
+            Err(format!(
+                "Invalid TLS certificate fingerprint \"{raw}\": expected 64 hex characters (SHA-256)"
+            ))
+        }
+    }
+}
+
+impl std::fmt::Display for TlsFingerprint {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for TlsFingerprint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        TlsFingerprint::parse(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(untagged)]
 pub enum ChainEndpoint {
-    Universal(String),
+    Universal(RpcUrl),
     Specific {
-        url: String,
+        url: RpcUrl,
         operations: Vec<EndpointAllowedOperation>,
+        /// SHA-256 fingerprint of this endpoint's TLS certificate. Parsed and
+        /// stored only: the connection is verified against the system's
+        /// normal root store whether or not this is set, and a certificate
+        /// that differs from the fingerprint is not rejected.
+        #[serde(default)]
+        tls_fingerprint: Option<TlsFingerprint>,
     },
 }
 
+impl ChainEndpoint {
+    fn url(&self) -> &RpcUrl {
+        match self {
+            ChainEndpoint::Universal(url)
+            | ChainEndpoint::Specific {
+                url, ..
+            } => url,
+        }
+    }
+
+    fn tls_fingerprint(&self) -> Option<&TlsFingerprint> {
+        match self {
+            ChainEndpoint::Universal(_) => None,
+            ChainEndpoint::Specific {
+                tls_fingerprint, ..
+            } => tls_fingerprint.as_ref(),
+        }
+    }
+}
+
+/// How a chain client learns about new blocks. Only consulted by the Asset
+/// Hub client today — Polygon already subscribes directly to `Transfer` logs
+/// rather than walking blocks one by one, so this has no effect there.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockSource {
+    /// Subscribe to finalized heads and process blocks as they're pushed.
+    /// Lowest latency, and the default.
+    #[default]
+    Subscription,
+    /// Poll for the latest finalized block on an interval instead, for nodes
+    /// or providers that don't support block subscriptions. Only the latest
+    /// polled block is processed — if more than one block lands between
+    /// polls, the earlier ones are silently skipped rather than backfilled.
+    Polling,
+}
+
 // TODO: add some docs for fields, their purpose might be not obvious
 #[derive(Deserialize, Clone, Debug, Default)]
 pub struct ChainConfig {
@@ -85,13 +226,40 @@ pub struct ChainConfig {
     /// Allow endpoints which starts from `http://` and `ws://` instead of `https://` and `wss://`
     #[serde(default = "default_allow_insecure_endpoints")]
     pub allow_insecure_endpoints: bool,
+    /// Number of additional blocks a transfer must be buried under on this
+    /// chain before it's reported as an incoming payment. Guards against
+    /// treating a transfer in a since-reorged block as final. 0 reports
+    /// transfers as soon as they're seen.
+    #[serde(default = "default_confirmations")]
+    pub confirmations: u64,
+    /// Per-asset overrides of `confirmations`, keyed by asset ID. Lets
+    /// operators require fewer confirmations for the chain's native asset
+    /// than for a bridged one, for example. An asset not listed here falls
+    /// back to `confirmations`.
+    #[serde(default)]
+    pub confirmation_overrides: HashMap<String, u64>,
+    /// How the Asset Hub client learns about new blocks. See [`BlockSource`].
+    #[serde(default)]
+    pub block_source: BlockSource,
 }
 
 impl ChainConfig {
+    /// Confirmation depth to require for `asset_id`: its override if one is
+    /// configured, otherwise the chain-wide `confirmations`.
+    pub fn confirmations_for(
+        &self,
+        asset_id: &str,
+    ) -> u64 {
+        self.confirmation_overrides
+            .get(asset_id)
+            .copied()
+            .unwrap_or(self.confirmations)
+    }
+
     fn get_endpoints_with_allowed_operation(
         &self,
         op: EndpointAllowedOperation,
-    ) -> impl Iterator<Item = &String> {
+    ) -> impl Iterator<Item = &RpcUrl> {
         self.endpoints
             .iter()
             .flat_map(move |ep| match ep {
@@ -99,25 +267,76 @@ impl ChainConfig {
                 ChainEndpoint::Specific {
                     url,
                     operations,
+                    ..
                 } if operations.contains(&op) => Some(url),
                 _ => None,
             })
     }
 
-    pub fn get_random_requests_endpoint(&self) -> Option<String> {
-        let mut rng = rand::rng();
+    /// All endpoints allowed for requests, shuffled so callers can fail over
+    /// from one to the next without always hammering the same endpoint first.
+    pub fn shuffled_requests_endpoints(&self) -> Vec<String> {
+        let mut endpoints: Vec<String> = self
+            .get_endpoints_with_allowed_operation(EndpointAllowedOperation::Requests)
+            .map(ToString::to_string)
+            .collect();
+        endpoints.shuffle(&mut rand::rng());
 
-        self.get_endpoints_with_allowed_operation(EndpointAllowedOperation::Requests)
-            .choose(&mut rng)
-            .cloned()
+        endpoints
     }
 
-    pub fn get_random_subscriptions_endpoint(&self) -> Option<String> {
-        let mut rng = rand::rng();
+    /// All endpoints allowed for subscriptions, shuffled so callers can fail
+    /// over from one to the next without always hammering the same endpoint
+    /// first.
+    pub fn shuffled_subscriptions_endpoints(&self) -> Vec<String> {
+        let mut endpoints: Vec<String> = self
+            .get_endpoints_with_allowed_operation(EndpointAllowedOperation::Subscriptions)
+            .map(ToString::to_string)
+            .collect();
+        endpoints.shuffle(&mut rand::rng());
+
+        endpoints
+    }
+
+    /// The pinned certificate fingerprint configured for `endpoint`, if any.
+    /// Chain clients are expected to check this before trusting a `wss://`
+    /// connection to the endpoint; see `ClientError::CertificateMismatch`.
+    /// Not yet called anywhere — the TLS verifier hook it's meant to feed
+    /// doesn't exist yet, see the TODOs in `chain_client`'s connection loops.
+    #[expect(dead_code)]
+    pub fn fingerprint_for(
+        &self,
+        endpoint: &str,
+    ) -> Option<&TlsFingerprint> {
+        self.endpoints
+            .iter()
+            .find(|ep| ep.url().0 == endpoint)
+            .and_then(ChainEndpoint::tls_fingerprint)
+    }
+
+    /// Validate that every configured endpoint's scheme matches
+    /// `allow_insecure_endpoints`: `wss://` is always allowed, `ws://` only
+    /// when insecure endpoints are explicitly allowed. Called at config load
+    /// so a misconfigured endpoint is rejected up front instead of only
+    /// surfacing as a connection failure at first use.
+    fn validate_endpoint_schemes(&self) -> Result<(), String> {
+        for endpoint in &self.endpoints {
+            let url = match endpoint {
+                ChainEndpoint::Universal(url)
+                | ChainEndpoint::Specific {
+                    url, ..
+                } => url,
+            };
+
+            if !self.allow_insecure_endpoints && url.0.starts_with("ws://") {
+                return Err(format!(
+                    "RPC endpoint URL \"{url}\" uses ws:// but allow_insecure_endpoints is not \
+                     set; use wss:// or set allow_insecure_endpoints to true"
+                ));
+            }
+        }
 
-        self.get_endpoints_with_allowed_operation(EndpointAllowedOperation::Subscriptions)
-            .choose(&mut rng)
-            .cloned()
+        Ok(())
     }
 }
 
@@ -186,11 +405,25 @@ impl ChainsConfig {
 
                 chain_config.endpoints = endpoints
                     .iter()
-                    .map(|s| ChainEndpoint::Universal(s.to_string()))
+                    .map(|s| {
+                        ChainEndpoint::Universal(
+                            RpcUrl::parse(s).expect("built-in default endpoint URL is valid"),
+                        )
+                    })
                     .collect();
             }
         }
     }
+
+    /// Validate every configured chain's endpoint URLs. See
+    /// [`ChainConfig::validate_endpoint_schemes`].
+    pub fn validate_endpoints(&self) -> Result<(), String> {
+        for chain_config in self.chains.values() {
+            chain_config.validate_endpoint_schemes()?;
+        }
+
+        Ok(())
+    }
 }
 
 fn default_chain() -> ChainType {
@@ -201,6 +434,10 @@ fn default_invoice_lifetime_millis() -> u64 {
     DEFAULT_INVOICE_LIFETIME_MILLIS
 }
 
+fn default_expiration_check_interval_millis() -> u64 {
+    DEFAULT_EXPIRATION_CHECK_INTERVAL_MILLIS
+}
+
 // TODO: add validations for that params. At least we have to ensure that they
 // are not negative. Ideally, we have to also validate their estimate price and
 // don't allow to exceed it some constant amount like 5 dollars or something
@@ -232,6 +469,12 @@ pub struct PaymentsConfig {
     /// Invoice lifetime in milliseconds. Default is 24 hours.
     #[serde(default = "default_invoice_lifetime_millis")]
     pub invoice_lifetime_millis: u64,
+    /// How often the expiration detector sweeps for expired invoices, in
+    /// milliseconds. Lower values catch an invoice crossing `valid_till`
+    /// sooner at the cost of more frequent database polling; default is
+    /// 10 seconds.
+    #[serde(default = "default_expiration_check_interval_millis")]
+    pub expiration_check_interval_millis: u64,
     /// Default chain to use for invoices. Default is Polkadot Asset Hub.
     #[serde(default = "default_chain")]
     pub default_chain: ChainType,
@@ -244,6 +487,34 @@ pub struct PaymentsConfig {
     /// set, default settings will be used.
     #[serde(default)]
     pub slippage_params: HashMap<ChainType, HashMap<String, SlippageParams>>,
+    /// Amount withheld from the payout swept off an invoice's payment
+    /// address, per chain. Protects against existential-deposit reaping
+    /// errors and leaves enough balance for the payout extrinsic's own fees
+    /// when the address holds no other funds to pay them from. Defaults to 0
+    /// for chains not listed here.
+    #[serde(default)]
+    pub sweep_fee_buffer: HashMap<ChainType, Decimal>,
+    /// How many recent invoice lifecycle events (created/paid/expired/reaped)
+    /// to retain in memory for post-mortem debugging. Default is 256.
+    #[serde(default = "default_recent_events_buffer_size")]
+    pub recent_events_buffer_size: usize,
+    /// Minimum invoice amount accepted per specific asset, in the asset's own
+    /// units. Invoices below the configured minimum for their asset are
+    /// rejected at creation. If not set for an asset, there's no minimum.
+    #[serde(default)]
+    pub minimum_invoice_amount: HashMap<ChainType, HashMap<String, Decimal>>,
+    /// Maximum number of an invoice's incoming transactions included in a
+    /// `Seen`/`Paid` webhook callback body, most recent first. Invoices that
+    /// receive many small payments would otherwise grow their callback body
+    /// without bound. Default is 100.
+    #[serde(default = "default_webhook_max_transactions")]
+    pub webhook_max_transactions: usize,
+    /// Maximum number of simultaneously tracked invoices (any non-terminal
+    /// status). Protects memory and per-invoice RPC/polling load under a
+    /// burst of orders. Expired or reaped invoices free up capacity. Default
+    /// is unset, meaning unlimited.
+    #[serde(default)]
+    pub max_watched_invoices: Option<usize>,
 }
 
 impl PaymentsConfig {
@@ -288,26 +559,42 @@ impl PaymentsConfig {
 
             match chain {
                 ChainType::PolkadotAssetHub => {
-                    // Validate Polkadot address (prefix 0)
-                    let account_id =
-                        subxt::utils::AccountId32::from_str(recipient).map_err(|_| {
+                    // Validate Polkadot address (prefix 0) and make sure it
+                    // was actually encoded for Polkadot, not merely a
+                    // validly-checksummed address from another SS58 network
+                    // (e.g. Kusama's prefix 2).
+                    let (found_prefix, account_id) =
+                        from_base58_string(recipient).map_err(|e| {
                             format!(
-                                "Invalid Polkadot address: {}",
+                                "Invalid Polkadot address: {} ({e})",
                                 recipient
                             )
                         })?;
 
+                    if found_prefix != 0 {
+                        return Err(format!(
+                            "Recipient address for chain {:?} is encoded for SS58 network {} \
+                             instead of Polkadot (0): {}",
+                            chain, found_prefix, recipient
+                        ));
+                    }
+
                     // Re-encode to ensure correct format
                     self.recipient.insert(
                         *chain,
-                        to_base58_string(account_id.0, 0),
+                        to_base58_string(account_id, POLKADOT_SS58_PREFIX),
                     );
                 },
                 ChainType::Polygon => {
                     // Validate Ethereum/Polygon address (0x-prefixed hex, 20 bytes)
                     let address = recipient
                         .parse::<alloy::primitives::Address>()
-                        .map_err(|_| format!("Invalid Polygon address: {}", recipient))?;
+                        .map_err(|e| {
+                            format!(
+                                "Invalid Polygon address: {} ({e})",
+                                recipient
+                            )
+                        })?;
 
                     // Store checksummed version for consistency
                     self.recipient
@@ -386,6 +673,30 @@ fn default_signature_max_age_secs() -> u64 {
     DEFAULT_SIGNATURE_MAX_AGE_SECS
 }
 
+fn default_webhook_timeout_secs() -> u64 {
+    DEFAULT_WEBHOOK_TIMEOUT_SECS
+}
+
+fn default_recent_events_buffer_size() -> usize {
+    DEFAULT_RECENT_EVENTS_BUFFER_SIZE
+}
+
+fn default_webhook_max_transactions() -> usize {
+    DEFAULT_WEBHOOK_MAX_TRANSACTIONS
+}
+
+fn default_webhook_max_concurrent_requests() -> usize {
+    DEFAULT_WEBHOOK_MAX_CONCURRENT_REQUESTS
+}
+
+fn default_webhook_content_type() -> String {
+    DEFAULT_WEBHOOK_CONTENT_TYPE.to_string()
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    DEFAULT_WEBHOOK_MAX_ATTEMPTS
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShopMetaConfig {
     pub shop_name: String,
@@ -402,6 +713,27 @@ pub struct ShopConfig {
     pub invoices_webhook_url: Option<String>,
     #[serde(default = "default_signature_max_age_secs")]
     pub signature_max_age_secs: u64,
+    /// How long to wait for a webhook endpoint to respond before giving up
+    /// and scheduling a retry. Default: 60 seconds.
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub webhook_timeout_secs: u64,
+    /// Maximum number of webhook deliveries `WebhookSender` keeps in flight
+    /// at once. Bounds memory and outbound connections under a burst of
+    /// settlements; excess pending events stay queued in the database and
+    /// are picked up once a slot frees. Default: 10.
+    #[serde(default = "default_webhook_max_concurrent_requests")]
+    pub webhook_max_concurrent_requests: usize,
+    /// `Content-Type` header sent with webhook deliveries. The body is
+    /// always the same JSON event payload - this only changes the label, for
+    /// merchant stacks that route incoming requests by content type rather
+    /// than inspecting the body. Default: `application/json`.
+    #[serde(default = "default_webhook_content_type")]
+    pub webhook_content_type: String,
+    /// How many failed delivery attempts (counting the first) a retriable
+    /// webhook failure (network error, 5xx, 408/429) gets before it's given
+    /// up on and dead-lettered, same as a non-retriable failure. Default: 10.
+    #[serde(default = "default_webhook_max_attempts")]
+    pub webhook_max_attempts: u32,
     #[serde(default)]
     pub private_api_base_url: Option<String>,
     #[serde(flatten)]
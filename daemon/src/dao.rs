@@ -272,7 +272,15 @@ impl DAO {
     }
 
     pub async fn begin_transaction(&self) -> DaoResult<DaoTransaction> {
-        let transaction = self.pool.begin().await?;
+        // `BEGIN IMMEDIATE` takes SQLite's write lock up front instead of
+        // deferring it to the first write statement, so two concurrent
+        // transactions can't both run a check (e.g. a payout count) before
+        // either commits its write - the second blocks here until the first
+        // finishes instead of racing it.
+        let transaction = self
+            .pool
+            .begin_with("BEGIN IMMEDIATE")
+            .await?;
 
         Ok(DaoTransaction {
             transaction: Mutex::new(transaction),
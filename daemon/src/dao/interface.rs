@@ -117,6 +117,12 @@ pub trait DaoInterface: Send + Sync + 'static {
         invoice_id: Uuid,
     ) -> Result<Option<Invoice>, DaoInvoiceError>;
 
+    /// Get an invoice by its merchant-provided `order_id`.
+    async fn get_invoice_by_order_id(
+        &self,
+        order_id: &str,
+    ) -> Result<Option<Invoice>, DaoInvoiceError>;
+
     /// Get an invoice with sum of related incoming transactions by its unique
     /// ID.
     async fn get_invoice_with_received_amount_by_id(
@@ -137,6 +143,13 @@ pub trait DaoInterface: Send + Sync + 'static {
         status: InvoiceStatus,
     ) -> Result<Invoice, DaoInvoiceError>;
 
+    /// Mark an invoice as seen (idempotent — `seen_at` keeps its first value
+    /// if already set; `updated_at` is still bumped).
+    async fn mark_invoice_seen(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<Invoice, DaoInvoiceError>;
+
     /// Update invoice data (amount, cart, `valid_till`).
     async fn update_invoice_data(
         &self,
@@ -277,11 +290,26 @@ pub trait DaoInterface: Send + Sync + 'static {
         limit: u32,
     ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError>;
 
+    async fn record_webhook_event_failure(
+        &self,
+        event_id: Uuid,
+        retry_meta: RetryMeta,
+        is_retriable: bool,
+    ) -> Result<WebhookEvent, DaoWebhookEventError>;
+
     async fn mark_webhook_event_as_sent(
         &self,
         event_id: Uuid,
     ) -> Result<WebhookEvent, DaoWebhookEventError>;
 
+    async fn get_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError>;
+
+    async fn replay_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError>;
+
     // === Changes Methods ===
 
     /// Get all invoices and related entities modified since the given
@@ -432,6 +460,11 @@ pub trait DaoTransactionInterface {
         invoice_id: Uuid,
     ) -> Result<Option<Invoice>, DaoInvoiceError>;
 
+    async fn get_invoice_by_order_id(
+        &self,
+        order_id: &str,
+    ) -> Result<Option<Invoice>, DaoInvoiceError>;
+
     async fn get_invoice_with_received_amount_by_id(
         &self,
         invoice_id: Uuid,
@@ -443,6 +476,11 @@ pub trait DaoTransactionInterface {
         status: InvoiceStatus,
     ) -> Result<Invoice, DaoInvoiceError>;
 
+    async fn mark_invoice_seen(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<Invoice, DaoInvoiceError>;
+
     async fn update_invoice_data(
         &self,
         data: UpdateInvoiceData,
@@ -563,11 +601,26 @@ pub trait DaoTransactionInterface {
         limit: u32,
     ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError>;
 
+    async fn record_webhook_event_failure(
+        &self,
+        event_id: Uuid,
+        retry_meta: RetryMeta,
+        is_retriable: bool,
+    ) -> Result<WebhookEvent, DaoWebhookEventError>;
+
     async fn mark_webhook_event_as_sent(
         &self,
         event_id: Uuid,
     ) -> Result<WebhookEvent, DaoWebhookEventError>;
 
+    async fn get_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError>;
+
+    async fn replay_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError>;
+
     // === Swap Methods ===
 
     async fn create_front_end_swap(
@@ -711,6 +764,13 @@ impl DaoInterface for DAO {
         DaoInvoiceMethods::get_invoice_by_id(self, invoice_id).await
     }
 
+    async fn get_invoice_by_order_id(
+        &self,
+        order_id: &str,
+    ) -> Result<Option<Invoice>, DaoInvoiceError> {
+        DaoInvoiceMethods::get_invoice_by_order_id(self, order_id).await
+    }
+
     async fn get_invoice_with_received_amount_by_id(
         &self,
         invoice_id: Uuid,
@@ -732,6 +792,13 @@ impl DaoInterface for DAO {
         DaoInvoiceMethods::update_invoice_status(self, invoice_id, status).await
     }
 
+    async fn mark_invoice_seen(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<Invoice, DaoInvoiceError> {
+        DaoInvoiceMethods::mark_invoice_seen(self, invoice_id).await
+    }
+
     async fn update_invoice_data(
         &self,
         data: UpdateInvoiceData,
@@ -911,6 +978,21 @@ impl DaoInterface for DAO {
         DaoWebhookEventMethods::get_webhook_events_to_send(self, limit).await
     }
 
+    async fn record_webhook_event_failure(
+        &self,
+        event_id: Uuid,
+        retry_meta: RetryMeta,
+        is_retriable: bool,
+    ) -> Result<WebhookEvent, DaoWebhookEventError> {
+        DaoWebhookEventMethods::record_webhook_event_failure(
+            self,
+            event_id,
+            retry_meta,
+            is_retriable,
+        )
+        .await
+    }
+
     async fn mark_webhook_event_as_sent(
         &self,
         event_id: Uuid,
@@ -918,6 +1000,18 @@ impl DaoInterface for DAO {
         DaoWebhookEventMethods::mark_webhook_event_as_sent(self, event_id).await
     }
 
+    async fn get_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError> {
+        DaoWebhookEventMethods::get_dead_letter_webhook_events(self).await
+    }
+
+    async fn replay_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError> {
+        DaoWebhookEventMethods::replay_dead_letter_webhook_events(self).await
+    }
+
     async fn get_invoice_changes(
         &self,
         since: DateTime<Utc>,
@@ -1102,6 +1196,13 @@ impl DaoTransactionInterface for DaoTransaction {
         DaoInvoiceMethods::get_invoice_by_id(self, invoice_id).await
     }
 
+    async fn get_invoice_by_order_id(
+        &self,
+        order_id: &str,
+    ) -> Result<Option<Invoice>, DaoInvoiceError> {
+        DaoInvoiceMethods::get_invoice_by_order_id(self, order_id).await
+    }
+
     async fn get_invoice_with_received_amount_by_id(
         &self,
         invoice_id: Uuid,
@@ -1117,6 +1218,13 @@ impl DaoTransactionInterface for DaoTransaction {
         DaoInvoiceMethods::update_invoice_status(self, invoice_id, status).await
     }
 
+    async fn mark_invoice_seen(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<Invoice, DaoInvoiceError> {
+        DaoInvoiceMethods::mark_invoice_seen(self, invoice_id).await
+    }
+
     async fn update_invoice_data(
         &self,
         data: UpdateInvoiceData,
@@ -1296,6 +1404,21 @@ impl DaoTransactionInterface for DaoTransaction {
         DaoWebhookEventMethods::get_webhook_events_to_send(self, limit).await
     }
 
+    async fn record_webhook_event_failure(
+        &self,
+        event_id: Uuid,
+        retry_meta: RetryMeta,
+        is_retriable: bool,
+    ) -> Result<WebhookEvent, DaoWebhookEventError> {
+        DaoWebhookEventMethods::record_webhook_event_failure(
+            self,
+            event_id,
+            retry_meta,
+            is_retriable,
+        )
+        .await
+    }
+
     async fn mark_webhook_event_as_sent(
         &self,
         event_id: Uuid,
@@ -1303,6 +1426,18 @@ impl DaoTransactionInterface for DaoTransaction {
         DaoWebhookEventMethods::mark_webhook_event_as_sent(self, event_id).await
     }
 
+    async fn get_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError> {
+        DaoWebhookEventMethods::get_dead_letter_webhook_events(self).await
+    }
+
+    async fn replay_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError> {
+        DaoWebhookEventMethods::replay_dead_letter_webhook_events(self).await
+    }
+
     async fn create_front_end_swap(
         &self,
         swap: CreateFrontEndSwapParams,
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use crate::dao::error_parsing::parse_update_not_allowed_error;
 use crate::types::{
+    ChainType,
     CreateInvoiceData,
     Invoice,
     InvoiceRow,
@@ -61,6 +62,45 @@ pub enum DaoInvoiceError {
     #[error("Order ID '{order_id}' already exists")]
     DuplicateOrderId { order_id: String },
 
+    /// Requested invoice amount isn't positive
+    #[error("Invoice amount must be positive, got {amount}")]
+    InvalidAmount { amount: rust_decimal::Decimal },
+
+    /// Requested invoice amount is below the configured minimum for the asset
+    #[error("Invoice amount {amount} is below the configured minimum of {minimum}")]
+    AmountBelowMinimum {
+        amount: rust_decimal::Decimal,
+        minimum: rust_decimal::Decimal,
+    },
+
+    /// Merchant-provided metadata exceeds the size limit
+    #[error("Invoice metadata must not exceed {max_size_bytes} bytes, got {size_bytes}")]
+    MetadataTooLarge {
+        size_bytes: usize,
+        max_size_bytes: usize,
+    },
+
+    /// `expected_sender` isn't a valid address for the invoice's chain
+    #[error("Invalid expected sender address '{address}': {reason}")]
+    InvalidExpectedSender { address: String, reason: String },
+
+    /// The configured asset id has no corresponding metadata on `chain`, so
+    /// a balance lookup against it would read as zero forever rather than
+    /// fail loudly
+    #[error("Asset '{asset_id}' has no metadata on chain {chain}")]
+    UnknownAsset { chain: ChainType, asset_id: String },
+
+    /// The configured `max_watched_invoices` cap has been reached
+    #[error("Maximum number of tracked invoices ({max}) has been reached")]
+    CapacityExceeded { max: usize },
+
+    /// A payout for this invoice is already waiting, in progress, or
+    /// scheduled for an automatic retry. Retrying a permanently failed sweep
+    /// is done by calling initiate_payout again once that's no longer true,
+    /// rather than by resurrecting the failed payout itself.
+    #[error("Invoice {invoice_id} already has an active payout")]
+    PayoutAlreadyInProgress { invoice_id: Uuid },
+
     /// Database operation failed
     #[error("Database error during invoice operation")]
     DatabaseError,
@@ -82,6 +122,27 @@ impl crate::api::ApiErrorExt for DaoInvoiceError {
             DaoInvoiceError::DuplicateOrderId {
                 ..
             } => "DUPLICATE_ENTITY",
+            DaoInvoiceError::InvalidAmount {
+                ..
+            } => "VALIDATION_ERROR",
+            DaoInvoiceError::AmountBelowMinimum {
+                ..
+            } => "VALIDATION_ERROR",
+            DaoInvoiceError::MetadataTooLarge {
+                ..
+            } => "VALIDATION_ERROR",
+            DaoInvoiceError::InvalidExpectedSender {
+                ..
+            } => "VALIDATION_ERROR",
+            DaoInvoiceError::UnknownAsset {
+                ..
+            } => "VALIDATION_ERROR",
+            DaoInvoiceError::CapacityExceeded {
+                ..
+            } => "CAPACITY_EXCEEDED",
+            DaoInvoiceError::PayoutAlreadyInProgress {
+                ..
+            } => "UPDATE_NOT_ALLOWED",
             DaoInvoiceError::DatabaseError => "INTERNAL_SERVER_ERROR",
         }
     }
@@ -100,6 +161,27 @@ impl crate::api::ApiErrorExt for DaoInvoiceError {
             DaoInvoiceError::DuplicateOrderId {
                 ..
             } => "INVOICE_DUPLICATE_ORDER_ID",
+            DaoInvoiceError::InvalidAmount {
+                ..
+            } => "INVOICE_INVALID_AMOUNT",
+            DaoInvoiceError::AmountBelowMinimum {
+                ..
+            } => "INVOICE_AMOUNT_BELOW_MINIMUM",
+            DaoInvoiceError::MetadataTooLarge {
+                ..
+            } => "INVOICE_METADATA_TOO_LARGE",
+            DaoInvoiceError::InvalidExpectedSender {
+                ..
+            } => "INVOICE_INVALID_EXPECTED_SENDER",
+            DaoInvoiceError::UnknownAsset {
+                ..
+            } => "INVOICE_UNKNOWN_ASSET",
+            DaoInvoiceError::CapacityExceeded {
+                ..
+            } => "INVOICE_CAPACITY_EXCEEDED",
+            DaoInvoiceError::PayoutAlreadyInProgress {
+                ..
+            } => "INVOICE_PAYOUT_ALREADY_IN_PROGRESS",
             DaoInvoiceError::DatabaseError => "INTERNAL_SERVER_ERROR",
         }
     }
@@ -118,6 +200,31 @@ impl crate::api::ApiErrorExt for DaoInvoiceError {
             DaoInvoiceError::DuplicateOrderId {
                 ..
             } => "An invoice with the specified order ID already exists.",
+            DaoInvoiceError::InvalidAmount {
+                ..
+            } => "The requested invoice amount must be positive.",
+            DaoInvoiceError::AmountBelowMinimum {
+                ..
+            } => "The requested invoice amount is below the configured minimum for this asset.",
+            DaoInvoiceError::MetadataTooLarge {
+                ..
+            } => "The invoice metadata exceeds the maximum allowed size.",
+            DaoInvoiceError::InvalidExpectedSender {
+                ..
+            } => "The expected sender address is not valid for this invoice's chain.",
+            DaoInvoiceError::UnknownAsset {
+                ..
+            } => "The configured asset id does not exist on this chain.",
+            DaoInvoiceError::CapacityExceeded {
+                ..
+            } => {
+                "The maximum number of simultaneously tracked invoices has been reached. Please retry later."
+            },
+            DaoInvoiceError::PayoutAlreadyInProgress {
+                ..
+            } => {
+                "The invoice already has a payout that is waiting, in progress, or scheduled for retry."
+            },
             DaoInvoiceError::DatabaseError => "A database error occurred.",
         }
     }
@@ -136,6 +243,27 @@ impl crate::api::ApiErrorExt for DaoInvoiceError {
             DaoInvoiceError::DuplicateOrderId {
                 ..
             } => reqwest::StatusCode::CONFLICT,
+            DaoInvoiceError::InvalidAmount {
+                ..
+            } => reqwest::StatusCode::BAD_REQUEST,
+            DaoInvoiceError::AmountBelowMinimum {
+                ..
+            } => reqwest::StatusCode::BAD_REQUEST,
+            DaoInvoiceError::MetadataTooLarge {
+                ..
+            } => reqwest::StatusCode::BAD_REQUEST,
+            DaoInvoiceError::InvalidExpectedSender {
+                ..
+            } => reqwest::StatusCode::BAD_REQUEST,
+            DaoInvoiceError::UnknownAsset {
+                ..
+            } => reqwest::StatusCode::BAD_REQUEST,
+            DaoInvoiceError::CapacityExceeded {
+                ..
+            } => reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            DaoInvoiceError::PayoutAlreadyInProgress {
+                ..
+            } => reqwest::StatusCode::CONFLICT,
             DaoInvoiceError::DatabaseError => reqwest::StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -199,8 +327,8 @@ pub trait DaoInvoiceMethods: DaoExecutor + 'static {
         let invoice: Invoice = invoice.into();
 
         let query = sqlx::query_as::<_, InvoiceRow>(
-        "INSERT INTO invoices (id, order_id, asset_id, asset_name, chain, amount, payment_address, status, cart, redirect_url, valid_till, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "INSERT INTO invoices (id, order_id, asset_id, asset_name, chain, amount, payment_address, status, cart, redirect_url, metadata, expected_sender, test, valid_till, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             RETURNING *"
         )
             .bind(invoice.id)
@@ -213,6 +341,9 @@ pub trait DaoInvoiceMethods: DaoExecutor + 'static {
             .bind(invoice.status)
             .bind(Json(invoice.cart))
             .bind(invoice.redirect_url)
+            .bind(invoice.metadata.map(Json))
+            .bind(invoice.expected_sender)
+            .bind(invoice.test)
             .bind(invoice.valid_till.naive_utc())
             .bind(invoice.created_at.naive_utc())
             .bind(invoice.updated_at.naive_utc());
@@ -290,6 +421,31 @@ pub trait DaoInvoiceMethods: DaoExecutor + 'static {
             })
     }
 
+    async fn get_invoice_by_order_id(
+        &self,
+        order_id: &str,
+    ) -> Result<Option<Invoice>, DaoInvoiceError> {
+        let query = sqlx::query_as::<_, InvoiceRow>(
+            "SELECT *
+            FROM invoices
+            WHERE order_id = ?",
+        )
+        .bind(order_id);
+
+        self.fetch_optional(query)
+            .await
+            .map_err(|e| {
+                tracing::debug!(
+                    error.category = "dao.invoice",
+                    error.operation = "get_invoice_by_order_id",
+                    %order_id,
+                    error.source = ?e,
+                    "Failed to fetch invoice by order_id"
+                );
+                DaoInvoiceError::DatabaseError
+            })
+    }
+
     async fn get_invoice_with_received_amount_by_id(
         &self,
         invoice_id: Uuid,
@@ -402,6 +558,43 @@ pub trait DaoInvoiceMethods: DaoExecutor + 'static {
             })
     }
 
+    // Idempotent: only sets `seen_at` the first time, so calling this again
+    // on an already-seen invoice (e.g. after a reorg briefly reverts
+    // `status`) leaves `seen_at` unchanged rather than erroring; only
+    // `updated_at` moves.
+    async fn mark_invoice_seen(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<Invoice, DaoInvoiceError> {
+        let query = sqlx::query_as::<_, InvoiceRow>(
+            "UPDATE invoices
+            SET seen_at = coalesce(seen_at, datetime('now')),
+                updated_at = datetime('now')
+            WHERE id = ?
+            RETURNING *",
+        )
+        .bind(invoice_id);
+
+        self.fetch_one(query)
+            .await
+            .map_err(|e| {
+                tracing::debug!(
+                    error.category = "dao.invoice",
+                    error.operation = "mark_invoice_seen",
+                    %invoice_id,
+                    error.source = ?e,
+                    "Failed to mark invoice as seen"
+                );
+
+                match e {
+                    sqlx::Error::RowNotFound => DaoInvoiceError::NotFound {
+                        invoice_id,
+                    },
+                    _ => DaoInvoiceError::DatabaseError,
+                }
+            })
+    }
+
     async fn update_invoice_data(
         &self,
         data: UpdateInvoiceData,
@@ -1,7 +1,10 @@
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::types::WebhookEvent;
+use crate::types::{
+    RetryMeta,
+    WebhookEvent,
+};
 
 use super::DaoExecutor;
 
@@ -11,6 +14,28 @@ pub enum DaoWebhookEventError {
     DatabaseError,
 }
 
+impl crate::api::ApiErrorExt for DaoWebhookEventError {
+    fn category(&self) -> &str {
+        "INTERNAL_SERVER_ERROR"
+    }
+
+    fn code(&self) -> &str {
+        match self {
+            DaoWebhookEventError::DatabaseError => "DATABASE_ERROR",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            DaoWebhookEventError::DatabaseError => "A database error occurred.",
+        }
+    }
+
+    fn http_status_code(&self) -> reqwest::StatusCode {
+        reqwest::StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
 pub trait DaoWebhookEventMethods: DaoExecutor + 'static {
     async fn create_webhook_event(
         &self,
@@ -23,6 +48,9 @@ pub trait DaoWebhookEventMethods: DaoExecutor + 'static {
              VALUES (?, ?, ?, ?, ?, ?)
              RETURNING *",
         )
+        // dead_letter, retry_count, last_attempt_at, next_retry_at and
+        // failure_message are left at their column defaults (unretried) for
+        // a freshly created event.
         .bind(event.id)
         .bind(event.entity_id)
         .bind(event.payload)
@@ -61,16 +89,22 @@ pub trait DaoWebhookEventMethods: DaoExecutor + 'static {
                 entity_id,
                 payload,
                 sent,
+                dead_letter,
                 created_at,
                 updated_at,
+                retry_count,
+                last_attempt_at,
+                next_retry_at,
+                failure_message,
                 ROW_NUMBER() OVER (
                   PARTITION BY entity_id
                   ORDER BY created_at ASC, id ASC
                 ) as rn
               FROM webhook_events
-              WHERE sent = 0
+              WHERE sent = 0 AND (next_retry_at IS NULL OR next_retry_at <= datetime('now'))
             )
-            SELECT id, entity_id, payload, sent, created_at, updated_at
+            SELECT id, entity_id, payload, sent, dead_letter, created_at, updated_at,
+                   retry_count, last_attempt_at, next_retry_at, failure_message
             FROM ranked_events
             WHERE rn = 1
             ORDER BY created_at ASC, id ASC
@@ -83,6 +117,62 @@ pub trait DaoWebhookEventMethods: DaoExecutor + 'static {
             .map_err(|_| DaoWebhookEventError::DatabaseError)
     }
 
+    /// Record a failed delivery attempt, persisting the backed-off
+    /// `next_retry_at` so the event isn't picked up again before then.
+    async fn record_webhook_event_failure(
+        &self,
+        event_id: Uuid,
+        retry_meta: RetryMeta,
+        is_retriable: bool,
+    ) -> Result<WebhookEvent, DaoWebhookEventError> {
+        // A non-retriable delivery gives up by marking the event as sent so
+        // it drops out of get_webhook_events_to_send, and as dead_letter so
+        // it stays distinguishable from an actually-delivered event for
+        // get_dead_letter_webhook_events/replay_dead_letter_webhook_events.
+        let query = sqlx::query_as::<_, WebhookEvent>(
+            "UPDATE webhook_events
+             SET retry_count = ?,
+                 last_attempt_at = ?,
+                 next_retry_at = ?,
+                 failure_message = ?,
+                 sent = ?,
+                 dead_letter = ?,
+                 updated_at = ?
+             WHERE id = ?
+             RETURNING *",
+        )
+        .bind(retry_meta.retry_count)
+        .bind(
+            retry_meta
+                .last_attempt_at
+                .map(|dt| dt.naive_utc()),
+        )
+        .bind(
+            retry_meta
+                .next_retry_at
+                .map(|dt| dt.naive_utc()),
+        )
+        .bind(&retry_meta.failure_message)
+        .bind(!is_retriable)
+        .bind(!is_retriable)
+        .bind(chrono::Utc::now().naive_utc())
+        .bind(event_id);
+
+        self.fetch_one(query)
+            .await
+            .map_err(|e| {
+                tracing::debug!(
+                    error.category = "dao.webhook_event",
+                    error.operation = "record_webhook_event_failure",
+                    error.source = ?e,
+                    event_id = %event_id,
+                    "Failed to record webhook event delivery failure"
+                );
+
+                DaoWebhookEventError::DatabaseError
+            })
+    }
+
     async fn mark_webhook_event_as_sent(
         &self,
         event_id: Uuid,
@@ -100,6 +190,66 @@ pub trait DaoWebhookEventMethods: DaoExecutor + 'static {
             .await
             .map_err(|_| DaoWebhookEventError::DatabaseError)
     }
+
+    /// List webhook events that were given up on (a non-retriable failure,
+    /// or a retriable one that exhausted its attempts), for an admin to
+    /// inspect or replay.
+    async fn get_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError> {
+        let query = sqlx::query_as::<_, WebhookEvent>(
+            "SELECT *
+             FROM webhook_events
+             WHERE dead_letter = 1
+             ORDER BY created_at ASC, id ASC",
+        );
+
+        self.fetch_all(query)
+            .await
+            .map_err(|e| {
+                tracing::debug!(
+                    error.category = "dao.webhook_event",
+                    error.operation = "get_dead_letter_webhook_events",
+                    error.source = ?e,
+                    "Failed to fetch dead-letter webhook events"
+                );
+
+                DaoWebhookEventError::DatabaseError
+            })
+    }
+
+    /// Reset all dead-lettered events back to retryable so the next
+    /// `WebhookSender` poll picks them up, clearing the retry backoff and
+    /// failure message accumulated from the attempts that gave up on them.
+    async fn replay_dead_letter_webhook_events(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError> {
+        let query = sqlx::query_as::<_, WebhookEvent>(
+            "UPDATE webhook_events
+             SET sent = 0,
+                 dead_letter = 0,
+                 retry_count = 0,
+                 next_retry_at = NULL,
+                 failure_message = NULL,
+                 updated_at = ?
+             WHERE dead_letter = 1
+             RETURNING *",
+        )
+        .bind(chrono::Utc::now().naive_utc());
+
+        self.fetch_all(query)
+            .await
+            .map_err(|e| {
+                tracing::debug!(
+                    error.category = "dao.webhook_event",
+                    error.operation = "replay_dead_letter_webhook_events",
+                    error.source = ?e,
+                    "Failed to replay dead-letter webhook events"
+                );
+
+                DaoWebhookEventError::DatabaseError
+            })
+    }
 }
 
 impl<T: DaoExecutor + 'static> DaoWebhookEventMethods for T {}
@@ -441,4 +591,66 @@ mod tests {
             .unwrap();
         assert_eq!(events.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_record_webhook_event_failure_retriable() {
+        let dao = create_test_dao().await;
+
+        let event = default_webhook_event(Uuid::new_v4());
+        let event_id = event.id;
+        dao.create_webhook_event(event)
+            .await
+            .unwrap();
+
+        let mut retry_meta = RetryMeta::default();
+        retry_meta.increment_retry("connection refused".to_string());
+
+        let updated = dao
+            .record_webhook_event_failure(event_id, retry_meta.clone(), true)
+            .await
+            .unwrap();
+
+        // Retriable failures leave the event unsent so it's retried later.
+        assert!(!updated.sent);
+        assert_eq!(updated.retry_meta.retry_count, 1);
+        assert_eq!(
+            updated.retry_meta.failure_message,
+            Some("connection refused".to_string())
+        );
+
+        // Still in the future, so it shouldn't be picked up yet.
+        let events = dao
+            .get_webhook_events_to_send(10)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_record_webhook_event_failure_permanent() {
+        let dao = create_test_dao().await;
+
+        let event = default_webhook_event(Uuid::new_v4());
+        let event_id = event.id;
+        dao.create_webhook_event(event)
+            .await
+            .unwrap();
+
+        let mut retry_meta = RetryMeta::default();
+        retry_meta.increment_retry("HTTP 400 Bad Request".to_string());
+
+        let updated = dao
+            .record_webhook_event_failure(event_id, retry_meta, false)
+            .await
+            .unwrap();
+
+        // Non-retriable failures give up by marking the event as sent.
+        assert!(updated.sent);
+
+        let events = dao
+            .get_webhook_events_to_send(10)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 0);
+    }
 }
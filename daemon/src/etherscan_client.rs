@@ -3,6 +3,10 @@ mod types;
 use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::{
+    DateTime,
+    Utc,
+};
 use governor::{
     DefaultDirectRateLimiter,
     Quota,
@@ -128,6 +132,11 @@ impl EtherscanClient {
         }
     }
 
+    /// Fetch an address's incoming transfers of `asset_id` on `chain`,
+    /// ignoring anything at or before `since` (normally the invoice's
+    /// `created_at`) so a re-derived address that happens to have prior
+    /// activity — or this same address from a previous invoice — doesn't
+    /// have unrelated pre-existing transfers attributed to the invoice.
     #[tracing::instrument(skip(self), fields(category = "etherscan_client"))]
     pub async fn get_account_incoming_transfers(
         &self,
@@ -135,6 +144,7 @@ impl EtherscanClient {
         asset_id: &str,
         address: &str,
         invoice_id: Uuid,
+        since: DateTime<Utc>,
     ) -> Result<Vec<IncomingTransaction>, EtherscanClientError> {
         let chain_id = match chain {
             ChainType::Polygon => 137,
@@ -149,6 +159,11 @@ impl EtherscanClient {
             .get_account_transfers(chain_id, asset_id, address)
             .await?
             .into_iter()
+            .filter(|trans| {
+                trans
+                    .timestamp()
+                    .is_none_or(|timestamp| timestamp > since)
+            })
             .filter_map(|trans| {
                 (trans.to.to_lowercase() == address.to_lowercase())
                     .then(|| trans.into_incoming_transaction(invoice_id))
@@ -1,3 +1,7 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
 use rust_decimal::Decimal;
 use serde::{
     Deserialize,
@@ -53,8 +57,8 @@ pub struct GetAccountTokenTransactionsParams<'a> {
 pub struct EtherscanTransaction {
     #[serde_as(as = "DisplayFromStr")]
     pub block_number: u32,
-    // #[serde(deserialize_with = "deserialize_string_to_u64")]
-    // pub time_stamp: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub time_stamp: i64,
     pub hash: String,
     // #[serde(deserialize_with = "deserialize_string_to_u32")]
     // pub nonce: u32,
@@ -83,6 +87,13 @@ pub struct EtherscanTransaction {
 }
 
 impl EtherscanTransaction {
+    /// When this transfer was included in a block, for filtering out
+    /// transfers that predate an invoice's payment address being put into
+    /// use (see `EtherscanClient::get_account_incoming_transfers`).
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.time_stamp, 0)
+    }
+
     #[expect(clippy::cast_possible_truncation)]
     pub fn into_incoming_transaction(
         self,
@@ -111,3 +122,55 @@ impl EtherscanTransaction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction_at(time_stamp: i64) -> EtherscanTransaction {
+        EtherscanTransaction {
+            block_number: 1,
+            time_stamp,
+            hash: "0xhash".to_string(),
+            from: "0xfrom".to_string(),
+            contract_address: "0xcontract".to_string(),
+            to: "0xto".to_string(),
+            value: 1,
+            token_symbol: "USDC".to_string(),
+            token_decimal: 6,
+            transaction_index: 0,
+        }
+    }
+
+    #[test]
+    fn timestamp_converts_unix_seconds() {
+        let transaction = transaction_at(1_700_000_000);
+
+        assert_eq!(
+            transaction.timestamp(),
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+    }
+
+    /// A transfer that landed before an invoice existed — e.g. a preexisting
+    /// balance on a re-derived address — must not be mistaken for a payment
+    /// towards that invoice. `EtherscanClient::get_account_incoming_transfers`
+    /// relies on exactly this `timestamp() > since` comparison to drop it.
+    #[test]
+    fn preexisting_transfer_is_excluded_by_since_filter() {
+        let since = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let preexisting = transaction_at(1_699_999_999);
+        let after_invoice_created = transaction_at(1_700_000_001);
+
+        assert!(
+            !preexisting
+                .timestamp()
+                .is_none_or(|timestamp| timestamp > since)
+        );
+        assert!(
+            after_invoice_created
+                .timestamp()
+                .is_none_or(|timestamp| timestamp > since)
+        );
+    }
+}
@@ -1,3 +1,8 @@
+use std::sync::Arc;
+use std::sync::atomic::{
+    AtomicU32,
+    Ordering,
+};
 use std::time::Duration;
 
 use kalatori_client::types::KalatoriEventExt;
@@ -16,6 +21,7 @@ use crate::dao::{
     DaoTransactionInterface,
 };
 use crate::types::{
+    ExpirationSweepStats,
     Invoice,
     InvoiceEventType,
     InvoiceStatus,
@@ -23,18 +29,43 @@ use crate::types::{
     Refund,
 };
 
-const EXPIRATION_CHECK_INTERVAL_MILLIS: u64 = 10_000;
-
 #[derive(Debug)]
 enum ExpirationDetectorError {
     DatabaseError,
 }
 
+/// Shared handle onto the count of invoices reaped by the most recently
+/// completed expiration sweep, cloned into [`crate::state::AppState`] so the
+/// introspection API can report it without a channel back into the running
+/// [`ExpirationDetector`] task.
+#[derive(Debug, Clone, Default)]
+pub struct ExpirationSweepCounter(Arc<AtomicU32>);
+
+impl ExpirationSweepCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(
+        &self,
+        count: u32,
+    ) {
+        self.0.store(count, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> ExpirationSweepStats {
+        ExpirationSweepStats {
+            last_reaped_count: self.0.load(Ordering::Relaxed),
+        }
+    }
+}
+
 pub struct ExpirationDetector<D: DaoInterface + 'static = DAO> {
     dao: D,
     registry: InvoiceRegistry,
     config: PaymentsConfig,
     balance_checker: BalanceChecker,
+    reaped_counter: ExpirationSweepCounter,
 }
 
 impl<D: DaoInterface + 'static> ExpirationDetector<D> {
@@ -43,12 +74,14 @@ impl<D: DaoInterface + 'static> ExpirationDetector<D> {
         registry: InvoiceRegistry,
         config: PaymentsConfig,
         balance_checker: BalanceChecker,
+        reaped_counter: ExpirationSweepCounter,
     ) -> Self {
         ExpirationDetector {
             dao,
             registry,
             config,
             balance_checker,
+            reaped_counter,
         }
     }
 
@@ -115,10 +148,12 @@ impl<D: DaoInterface + 'static> ExpirationDetector<D> {
             .await
             .map_err(|_e| ExpirationDetectorError::DatabaseError)?;
 
-        let event = invoice
+        let public_event = invoice
             .into_public_invoice(&self.config.payment_url_base)
-            .build_event(InvoiceEventType::Expired)
-            .into();
+            .build_event(InvoiceEventType::Expired);
+        self.registry
+            .publish_event(public_event.clone());
+        let event = public_event.into();
 
         dao_transaction
             .create_webhook_event(event)
@@ -133,6 +168,9 @@ impl<D: DaoInterface + 'static> ExpirationDetector<D> {
         self.registry
             .remove_invoice(&invoice_id)
             .await;
+        self.registry
+            .record_invoice_expired(invoice_id)
+            .await;
 
         tracing::info!("Invoice has been marked as expired");
 
@@ -162,6 +200,12 @@ impl<D: DaoInterface + 'static> ExpirationDetector<D> {
             );
         }
 
+        // Invoices that are still paid as of the latest balance check are not
+        // counted here: they got paid in the same detection cycle they would
+        // have expired in, so they're left for the normal paid flow instead.
+        let mut timed_out_unpaid = 0u32;
+        let mut timed_out_partially_paid = 0u32;
+
         for invoice in expired_invoices {
             let invoice_id = invoice.id;
 
@@ -174,6 +218,9 @@ impl<D: DaoInterface + 'static> ExpirationDetector<D> {
                     // Check only final, it should be enough as long as we fetch only Waiting
                     // invoices here
                     if !invoice.invoice.status.is_final() {
+                        let was_partially_paid =
+                            invoice.invoice.status == InvoiceStatus::PartiallyPaid;
+
                         if let Err(e) = self
                             .update_invoice_expired(invoice)
                             .await
@@ -184,6 +231,13 @@ impl<D: DaoInterface + 'static> ExpirationDetector<D> {
                                 "Failed to update invoice status to Expired in database, will retry later"
                             );
                         } else {
+                            if was_partially_paid {
+                                timed_out_partially_paid =
+                                    timed_out_partially_paid.saturating_add(1);
+                            } else {
+                                timed_out_unpaid = timed_out_unpaid.saturating_add(1);
+                            }
+
                             tracing::info!(
                                 %invoice_id,
                                 "Expired invoice has been processed successfully"
@@ -207,6 +261,17 @@ impl<D: DaoInterface + 'static> ExpirationDetector<D> {
                 ),
             };
         }
+
+        if timed_out_unpaid > 0 || timed_out_partially_paid > 0 {
+            tracing::info!(
+                timed_out_unpaid,
+                timed_out_partially_paid,
+                "Invoices have timed out without being fully paid"
+            );
+        }
+
+        self.reaped_counter
+            .record(timed_out_unpaid.saturating_add(timed_out_partially_paid));
     }
 
     #[tracing::instrument(skip_all, fields(category = "expiration_detector"))]
@@ -215,7 +280,8 @@ impl<D: DaoInterface + 'static> ExpirationDetector<D> {
         token: CancellationToken,
     ) {
         let mut interval = interval(Duration::from_millis(
-            EXPIRATION_CHECK_INTERVAL_MILLIS,
+            self.config
+                .expiration_check_interval_millis,
         ));
 
         loop {
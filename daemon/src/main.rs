@@ -20,6 +20,7 @@ use std::collections::{
     HashSet,
 };
 use std::process::ExitCode;
+use std::time::Duration;
 
 use kalatori_client::types::ChainType;
 use kalatori_client::utils::HmacConfig;
@@ -63,7 +64,10 @@ use error::{
     PrettyCause,
 };
 use etherscan_client::EtherscanClient;
-use expiration_detector::ExpirationDetector;
+use expiration_detector::{
+    ExpirationDetector,
+    ExpirationSweepCounter,
+};
 use state::AppState;
 use swaps::{
     SwapsExecutor,
@@ -150,8 +154,11 @@ fn try_main(shutdown_notification: ShutdownNotification) -> Result<(), Error> {
         .block_on(async_try_main(shutdown_notification))
 }
 
-async fn init_invoice_registry(dao: &impl DaoInterface) -> Result<InvoiceRegistry, Error> {
-    let invoice_registry = InvoiceRegistry::new();
+async fn init_invoice_registry(
+    dao: &impl DaoInterface,
+    recent_events_capacity: usize,
+) -> Result<InvoiceRegistry, Error> {
+    let invoice_registry = InvoiceRegistry::new(recent_events_capacity);
 
     let restore_invoices = dao
         .get_active_invoices_with_amounts()
@@ -165,6 +172,56 @@ async fn init_invoice_registry(dao: &impl DaoInterface) -> Result<InvoiceRegistr
     Ok(invoice_registry)
 }
 
+/// Re-check every restored invoice's balance against the chain before the
+/// live trackers start, so payments that landed while the daemon was down
+/// aren't missed. Bounded by the restored invoice set itself (no separate
+/// block-range cap needed), and failures are logged and skipped rather than
+/// treated as fatal: the invoice stays tracked and the normal live tracker
+/// or the next expiration sweep will pick it up.
+#[expect(clippy::arithmetic_side_effects)]
+async fn backfill_invoice_balances<D: DaoInterface>(
+    invoice_registry: &InvoiceRegistry,
+    balance_checker: &BalanceChecker<D>,
+) {
+    let invoice_ids = invoice_registry.invoice_ids().await;
+
+    if invoice_ids.is_empty() {
+        tracing::info!("No restored invoices to backfill balances for");
+        return;
+    }
+
+    tracing::info!(
+        invoices_count = invoice_ids.len(),
+        "Backfilling balances for restored invoices before starting live tracking"
+    );
+
+    for (index, invoice_id) in invoice_ids.iter().enumerate() {
+        if let Err(e) = balance_checker
+            .check_invoice_balance(*invoice_id)
+            .await
+        {
+            tracing::warn!(
+                %invoice_id,
+                error = ?e,
+                "Failed to backfill invoice balance on startup, will retry via normal tracking"
+            );
+        }
+
+        if (index + 1) % 50 == 0 {
+            tracing::info!(
+                checked = index + 1,
+                total = invoice_ids.len(),
+                "Startup balance backfill in progress"
+            );
+        }
+    }
+
+    tracing::info!(
+        invoices_count = invoice_ids.len(),
+        "Startup balance backfill complete"
+    );
+}
+
 fn validate_and_extend_configs(
     chains_config: &mut ChainsConfig,
     payments_config: &mut PaymentsConfig,
@@ -185,6 +242,10 @@ fn validate_and_extend_configs(
         .validate_recipients(&required_recipients)
         .map_err(|_| Error::Fatal)?;
 
+    chains_config
+        .validate_endpoints()
+        .map_err(|_| Error::Fatal)?;
+
     // Extend chains config with default and restored asset IDs
     chains_config.add_default_asset_ids(&payments_config.default_asset_id);
     chains_config.add_restored_asset_ids(restored_asset_ids);
@@ -238,7 +299,11 @@ async fn async_try_main(shutdown_notification: ShutdownNotification) -> Result<(
     // Initialize DAO for SQLite database operations
     let dao = DAO::new(database_config.clone()).await?;
 
-    let invoice_registry = init_invoice_registry(&dao).await?;
+    let invoice_registry = init_invoice_registry(
+        &dao,
+        payments_config.recent_events_buffer_size,
+    )
+    .await?;
 
     validate_and_extend_configs(
         &mut chains_config,
@@ -337,11 +402,16 @@ async fn async_try_main(shutdown_notification: ShutdownNotification) -> Result<(
         transactions_recorder.clone(),
     );
 
+    backfill_invoice_balances(&invoice_registry, &balance_checker).await;
+
+    let expiration_sweep_counter = ExpirationSweepCounter::new();
+
     let expiration_detector = ExpirationDetector::new(
         dao.clone(),
         invoice_registry.clone(),
         payments_config.clone(),
         balance_checker.clone(),
+        expiration_sweep_counter.clone(),
     );
 
     let expiration_detector_handle =
@@ -393,6 +463,10 @@ async fn async_try_main(shutdown_notification: ShutdownNotification) -> Result<(
         dao.clone(),
         shop_config.invoices_webhook_url.clone(),
         hmac_config.clone(),
+        Duration::from_secs(shop_config.webhook_timeout_secs),
+        shop_config.webhook_max_concurrent_requests,
+        shop_config.webhook_content_type.clone(),
+        shop_config.webhook_max_attempts,
     );
 
     let webhook_sender_handle = webhook_sender.ignite(shutdown_notification.token.clone());
@@ -400,20 +474,27 @@ async fn async_try_main(shutdown_notification: ShutdownNotification) -> Result<(
     let swaps_tracker = SwapsTracker::new(
         dao.clone(),
         swaps_clients,
-        balance_checker,
+        balance_checker.clone(),
     );
 
     let swaps_tracker_handle = swaps_tracker.ignite(shutdown_notification.token.clone());
 
+    let instance_id = utils::instance_id::load_or_create(std::path::Path::new(
+        &database_config.dir,
+    ));
+
     let app_state = AppState::new(
         keyring_client,
         dao,
         invoice_registry,
         swaps_executor,
+        balance_checker,
         asset_names_map,
         payments_config,
         shop_config,
         secrets_config.api_secret_key,
+        instance_id,
+        expiration_sweep_counter,
     );
 
     let api_handle = api::api_server(
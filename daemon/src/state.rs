@@ -25,8 +25,16 @@ use kalatori_client::types::{
 use zip::ZipWriter;
 use zip::write::SimpleFileOptions;
 
+use crate::balance_checker::{
+    BalanceChecker,
+    BalanceCheckerError,
+};
 use crate::chain::InvoiceRegistry;
-use crate::chain::utils::to_base58_string;
+use crate::chain::utils::{
+    POLKADOT_SS58_PREFIX,
+    from_base58_string,
+    to_base58_string,
+};
 use crate::chain_client::{
     GenerateAddressData,
     KeyringClient,
@@ -49,14 +57,18 @@ use crate::dao::{
     DaoSwapError,
     DaoTransactionError,
     DaoTransactionInterface,
+    DaoWebhookEventError,
 };
+use crate::expiration_detector::ExpirationSweepCounter;
 use crate::swaps::SwapsExecutor;
 use crate::types::{
     ChainType,
     ChangesResponse,
     CreateFrontEndSwapParams,
     CreateInvoiceData,
+    ExpirationSweepStats,
     FrontEndSwap,
+    GenericEvent,
     InvoiceChanges,
     InvoiceEventType,
     InvoiceWithReceivedAmount,
@@ -70,30 +82,43 @@ use crate::types::{
     PaginatedResponse,
     Payout,
     PayoutChanges,
+    PayoutStatus,
     PublicAssetDescription,
+    PublicChainTip,
     PublicChangesResponse,
+    PublicRecentEvent,
     PublicSwap,
     PublicTransaction,
     RefundChanges,
+    ServerInfo,
     ShopPlatform,
     Swap,
     Transaction,
     TransferDestinationParams,
     UpdateInvoiceData,
+    WebhookEvent,
 };
 
 pub use swaps::SwapRequestError;
 
+/// Maximum size, in serialized bytes, of an invoice's merchant-provided
+/// `metadata` JSON value. Bounds how much opaque data a merchant can push
+/// into our database per invoice.
+const MAX_METADATA_SIZE_BYTES: usize = 4096;
+
 pub struct AppState<D: DaoInterface = DAO> {
     keyring: KeyringClient,
     dao: D,
     registry: InvoiceRegistry,
     swaps_executor: SwapsExecutor<D>,
+    balance_checker: BalanceChecker<D>,
     github_client: GithubClient,
-    asset_names_map: HashMap<String, String>,
+    asset_names_map: HashMap<(ChainType, String), String>,
     payments_config: PaymentsConfig,
     shop_config: ShopConfig,
     api_secret_key: SecretString,
+    instance_id: Uuid,
+    expiration_sweep_counter: ExpirationSweepCounter,
 }
 
 impl<D: DaoInterface> AppState<D> {
@@ -103,10 +128,13 @@ impl<D: DaoInterface> AppState<D> {
         dao: D,
         registry: InvoiceRegistry,
         swaps_executor: SwapsExecutor<D>,
-        asset_names_map: HashMap<String, String>,
+        balance_checker: BalanceChecker<D>,
+        asset_names_map: HashMap<(ChainType, String), String>,
         payments_config: PaymentsConfig,
         shop_config: ShopConfig,
         api_secret_key: SecretString,
+        instance_id: Uuid,
+        expiration_sweep_counter: ExpirationSweepCounter,
     ) -> Self {
         let github_client = GithubClient::new();
 
@@ -115,14 +143,95 @@ impl<D: DaoInterface> AppState<D> {
             dao,
             registry,
             swaps_executor,
+            balance_checker,
             github_client,
             asset_names_map,
             payments_config,
             shop_config,
             api_secret_key,
+            instance_id,
+            expiration_sweep_counter,
         }
     }
 
+    /// Force an immediate balance recheck for one invoice against the chain,
+    /// bypassing the normal wait for the next block/subscription event.
+    /// Operators use this when they know a payment landed but the watcher
+    /// missed it (e.g. after an RPC gap), as a manual escape hatch distinct
+    /// from `ExpirationDetector`'s periodic reconciliation pass. Any
+    /// newly-discovered transactions are recorded and fire their usual
+    /// webhook events as a side effect.
+    pub async fn recheck_invoice_balance(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<PublicInvoice, BalanceCheckerError> {
+        let invoice = self
+            .balance_checker
+            .check_invoice_balance(invoice_id)
+            .await?;
+
+        Ok(self.invoice_to_public_invoice(invoice))
+    }
+
+    /// Version and instance id of the currently running daemon process, for
+    /// the `/public/health` endpoint.
+    pub fn server_info(&self) -> ServerInfo {
+        ServerInfo::new(self.instance_id)
+    }
+
+    /// Snapshot of the expiration detector's last completed sweep, for the
+    /// `/internal/expiration-sweep` monitoring endpoint.
+    pub fn expiration_sweep_stats(&self) -> ExpirationSweepStats {
+        self.expiration_sweep_counter.stats()
+    }
+
+    /// Invoice lifecycle counters as Prometheus text exposition format, for
+    /// the `/internal/metrics` scrape endpoint.
+    pub async fn render_metrics(&self) -> String {
+        self.registry.render_metrics().await
+    }
+
+    /// The last block each chain's watcher has actually ingested, for the
+    /// `/internal/chain-tip` monitoring endpoint.
+    pub async fn chain_tip(&self) -> Vec<PublicChainTip> {
+        let mut tips = Vec::with_capacity(2);
+
+        for chain in [ChainType::PolkadotAssetHub, ChainType::Polygon] {
+            let tip = self
+                .balance_checker
+                .chain_tip(chain)
+                .await;
+            let block_time_estimate_millis = self
+                .balance_checker
+                .block_time_estimate_millis(chain)
+                .await;
+            let spec_version = self.balance_checker.spec_version(chain);
+            tips.push(PublicChainTip {
+                chain,
+                block_number: tip.as_ref().map(|tip| tip.block_number),
+                block_hash: tip
+                    .as_ref()
+                    .map(|tip| tip.block_hash.clone()),
+                timestamp: tip.as_ref().map(|tip| tip.timestamp),
+                block_time_estimate_millis,
+                spec_version,
+            });
+        }
+
+        tips
+    }
+
+    /// The most recent invoice lifecycle events (created/paid/expired/reaped),
+    /// oldest first, for the `/internal/recent-events` debugging endpoint.
+    pub async fn recent_events(&self) -> Vec<PublicRecentEvent> {
+        self.registry
+            .recent_events()
+            .await
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
     pub fn invoice_to_public_invoice(
         &self,
         invoice: InvoiceWithReceivedAmount,
@@ -130,6 +239,15 @@ impl<D: DaoInterface> AppState<D> {
         invoice.into_public_invoice(&self.payments_config.payment_url_base)
     }
 
+    /// Subscribe to invoice status change events as they happen, for
+    /// embedding applications that want to react in-process (e.g. to drive a
+    /// websocket) without going through the HTTP webhook delivery pipeline.
+    pub fn subscribe_invoice_events(
+        &self
+    ) -> tokio::sync::broadcast::Receiver<GenericEvent<PublicInvoice>> {
+        self.registry.subscribe()
+    }
+
     #[tracing::instrument(skip_all)]
     pub async fn get_invoice(
         &self,
@@ -147,11 +265,116 @@ impl<D: DaoInterface> AppState<D> {
         &self,
         params: CreateInvoiceParams,
     ) -> Result<InvoiceWithReceivedAmount, DaoInvoiceError> {
-        let id = Uuid::new_v4();
+        // `Decimal` has no NaN/infinite representation, so the only invalid
+        // amounts to guard against here are zero and negative ones - a
+        // zero-amount invoice would otherwise be instantly "paid" by an
+        // empty account.
+        if params.amount <= Decimal::ZERO {
+            return Err(DaoInvoiceError::InvalidAmount {
+                amount: params.amount,
+            });
+        }
+
+        if let Some(metadata) = &params.metadata {
+            let size_bytes = serde_json::to_vec(metadata)
+                .map_err(|_| DaoInvoiceError::DatabaseError)?
+                .len();
+
+            if size_bytes > MAX_METADATA_SIZE_BYTES {
+                return Err(DaoInvoiceError::MetadataTooLarge {
+                    size_bytes,
+                    max_size_bytes: MAX_METADATA_SIZE_BYTES,
+                });
+            }
+        }
+
         // Later we can extend CreateInvoiceParams to include optional chain and
         // asset_id
         let chain = self.payments_config.default_chain;
 
+        // Normalize to the same address format the chain watcher compares
+        // `transfer_info.source_address` against, so a merchant can't supply
+        // a technically-valid-but-differently-encoded address that would
+        // then silently never match. Done before the idempotency check below
+        // so that check compares against the same normalized form already
+        // stored on a prior attempt's invoice.
+        let expected_sender = params
+            .expected_sender
+            .map(|address| match chain {
+                ChainType::PolkadotAssetHub => {
+                    let (_prefix, account_id) = from_base58_string(&address).map_err(|e| {
+                        DaoInvoiceError::InvalidExpectedSender {
+                            address: address.clone(),
+                            reason: e.to_string(),
+                        }
+                    })?;
+
+                    Ok(to_base58_string(
+                        account_id,
+                        POLKADOT_SS58_PREFIX,
+                    ))
+                },
+                ChainType::Polygon => address
+                    .parse::<alloy::primitives::Address>()
+                    .map(|parsed| parsed.to_checksum(None))
+                    .map_err(
+                        |e| DaoInvoiceError::InvalidExpectedSender {
+                            address,
+                            reason: e.to_string(),
+                        },
+                    ),
+            })
+            .transpose()?;
+
+        // Treat a retry with an identical order_id as an idempotent no-op
+        // instead of failing on the order_id UNIQUE constraint, so a client
+        // that resends a timed-out creation request gets back the invoice it
+        // already created rather than a duplicate. metadata and
+        // expected_sender are part of that comparison too, so a retry that
+        // changes either of them is treated as a genuine conflict instead of
+        // silently discarding the new values.
+        if let Some(existing) = self
+            .dao
+            .get_invoice_by_order_id(&params.order_id)
+            .await?
+        {
+            if existing.amount == params.amount
+                && existing.cart == params.cart
+                && existing.metadata == params.metadata
+                && existing.expected_sender == expected_sender
+            {
+                return self
+                    .dao
+                    .get_invoice_with_received_amount_by_id(existing.id)
+                    .await?
+                    .ok_or(DaoInvoiceError::NotFound {
+                        invoice_id: existing.id,
+                    });
+            }
+
+            return Err(DaoInvoiceError::DuplicateOrderId {
+                order_id: params.order_id,
+            });
+        }
+
+        // Only the "create new" path below actually grows the watched-invoice
+        // set, so the capacity check has to come after the idempotency lookup
+        // above - otherwise a client retrying create_invoice for an
+        // already-created order_id could get wrongly rejected once the cap is
+        // reached, even though no new tracked invoice would result.
+        if let Some(max) = self
+            .payments_config
+            .max_watched_invoices
+        {
+            if self.registry.invoices_count().await >= max {
+                return Err(DaoInvoiceError::CapacityExceeded {
+                    max,
+                });
+            }
+        }
+
+        let id = Uuid::new_v4();
+
         let asset_id = self
             .payments_config
             .default_asset_id
@@ -159,12 +382,33 @@ impl<D: DaoInterface> AppState<D> {
             .unwrap()
             .clone();
 
+        if let Some(minimum) = self
+            .payments_config
+            .minimum_invoice_amount
+            .get(&chain)
+            .and_then(|minimums| minimums.get(&asset_id))
+        {
+            if params.amount < *minimum {
+                return Err(DaoInvoiceError::AmountBelowMinimum {
+                    amount: params.amount,
+                    minimum: *minimum,
+                });
+            }
+        }
+
+        // An asset id with no metadata entry isn't just cosmetically
+        // "UNKNOWN" — it means a balance lookup against it reads as zero
+        // forever, so an invoice using it could never be detected as paid.
+        // Catch a config typo here instead of letting it surface later as a
+        // silently-unpayable invoice.
         let asset_name = self
             .asset_names_map
-            .get(&asset_id)
+            .get(&(chain, asset_id.clone()))
             .cloned()
-            // This should never happen, but just in case
-            .unwrap_or_else(|| "UNKNOWN".to_string());
+            .ok_or(DaoInvoiceError::UnknownAsset {
+                chain,
+                asset_id: asset_id.clone(),
+            })?;
 
         let valid_till = Utc::now()
             + Duration::milliseconds(
@@ -191,7 +435,7 @@ impl<D: DaoInterface> AppState<D> {
                         DaoInvoiceError::DatabaseError
                     })?;
 
-                to_base58_string(account_id.0, 0)
+                to_base58_string(account_id.0, POLKADOT_SS58_PREFIX)
             },
             ChainType::Polygon => {
                 let derivation_params = vec![id.to_string()];
@@ -223,6 +467,9 @@ impl<D: DaoInterface> AppState<D> {
             amount: params.amount,
             cart: params.cart,
             redirect_url: params.redirect_url,
+            metadata: params.metadata,
+            expected_sender,
+            test: params.test,
             id,
             asset_id,
             asset_name,
@@ -243,10 +490,12 @@ impl<D: DaoInterface> AppState<D> {
             .await?;
 
         let invoice_with_amount = invoice.with_amount(Decimal::ZERO);
-        let event = self
+        let public_event = self
             .invoice_to_public_invoice(invoice_with_amount.clone())
-            .build_event(InvoiceEventType::Created)
-            .into();
+            .build_event(InvoiceEventType::Created);
+        self.registry
+            .publish_event(public_event.clone());
+        let event = public_event.into();
 
         dao_transaction
             .create_webhook_event(event)
@@ -266,6 +515,9 @@ impl<D: DaoInterface> AppState<D> {
         self.registry
             .add_invoice(invoice_with_amount.clone())
             .await;
+        self.registry
+            .record_invoice_created(invoice_with_amount.invoice.id)
+            .await;
 
         Ok(invoice_with_amount)
     }
@@ -275,6 +527,12 @@ impl<D: DaoInterface> AppState<D> {
         &self,
         params: UpdateInvoiceParams,
     ) -> Result<InvoiceWithReceivedAmount, DaoInvoiceError> {
+        if params.amount <= Decimal::ZERO {
+            return Err(DaoInvoiceError::InvalidAmount {
+                amount: params.amount,
+            });
+        }
+
         let data = UpdateInvoiceData {
             invoice_id: params.invoice_id,
             amount: params.amount,
@@ -298,10 +556,12 @@ impl<D: DaoInterface> AppState<D> {
         let invoice_with_amount = result
             .clone()
             .with_amount(Decimal::ZERO);
-        let event = self
+        let public_event = self
             .invoice_to_public_invoice(invoice_with_amount)
-            .build_event(InvoiceEventType::Updated)
-            .into();
+            .build_event(InvoiceEventType::Updated);
+        self.registry
+            .publish_event(public_event.clone());
+        let event = public_event.into();
 
         dao_transaction
             .create_webhook_event(event)
@@ -347,10 +607,12 @@ impl<D: DaoInterface> AppState<D> {
                 .await?;
 
             let invoice_with_amount = result.with_amount(invoice_with_amount.total_received_amount);
-            let event = self
+            let public_event = self
                 .invoice_to_public_invoice(invoice_with_amount.clone())
-                .build_event(InvoiceEventType::AdminCanceled)
-                .into();
+                .build_event(InvoiceEventType::AdminCanceled);
+            self.registry
+                .publish_event(public_event.clone());
+            let event = public_event.into();
 
             dao_transaction
                 .create_webhook_event(event)
@@ -368,10 +630,12 @@ impl<D: DaoInterface> AppState<D> {
                 .await?;
 
             let invoice_with_amount = result.with_amount(Decimal::ZERO);
-            let event = self
+            let public_event = self
                 .invoice_to_public_invoice(invoice_with_amount.clone())
-                .build_event(InvoiceEventType::AdminCanceled)
-                .into();
+                .build_event(InvoiceEventType::AdminCanceled);
+            self.registry
+                .publish_event(public_event.clone());
+            let event = public_event.into();
 
             dao_transaction
                 .create_webhook_event(event)
@@ -442,6 +706,12 @@ impl<D: DaoInterface> AppState<D> {
         ))
     }
 
+    /// Manually start a payout for `invoice_id`. Also how operators retry a
+    /// sweep that ended up in `PayoutStatus::Failed`: that status is terminal
+    /// (no DB-level transition out of it), so retrying means starting a new
+    /// payout rather than resurrecting the old one. Rejected if the invoice
+    /// already has another payout waiting, in progress, or scheduled for
+    /// automatic retry, to avoid sweeping the same funds twice.
     #[tracing::instrument(skip_all)]
     pub async fn initiate_payout(
         &self,
@@ -481,10 +751,75 @@ impl<D: DaoInterface> AppState<D> {
             Decimal::new(21, 2),
         );
 
-        self.dao
+        // The count-then-insert below has to be serialized against any other
+        // concurrent initiate_payout for the same invoice (an operator retry
+        // racing the automatic sweep path, or two operator clicks), so it
+        // runs inside a single transaction rather than as two separate pool
+        // queries: begin_transaction takes SQLite's write lock immediately
+        // (see DAO::begin_transaction), so a second concurrent call blocks
+        // here until the first has committed and sees its payout in the count.
+        let dao_transaction = self
+            .dao
+            .begin_transaction()
+            .await
+            .map_err(|_e| DaoInvoiceError::DatabaseError)?;
+
+        let active_payouts = dao_transaction
+            .count_payouts(&ListPayoutsParams {
+                status: Some(vec![
+                    PayoutStatus::Waiting,
+                    PayoutStatus::InProgress,
+                    PayoutStatus::FailedRetriable,
+                ]),
+                invoice_id: Some(invoice_id),
+                ..Default::default()
+            })
+            .await
+            .map_err(|_e| DaoInvoiceError::DatabaseError)?;
+
+        if active_payouts > 0 {
+            return Err(
+                DaoInvoiceError::PayoutAlreadyInProgress {
+                    invoice_id,
+                },
+            )
+        }
+
+        let payout = dao_transaction
             .create_payout(payout)
             .await
-            .map_err(|_e| DaoInvoiceError::DatabaseError)
+            .map_err(|_e| DaoInvoiceError::DatabaseError)?;
+
+        dao_transaction
+            .commit()
+            .await
+            .map_err(|_e| DaoInvoiceError::DatabaseError)?;
+
+        Ok(payout)
+    }
+
+    /// Dead-lettered webhook deliveries, for an operator to review before
+    /// deciding whether to replay them.
+    #[tracing::instrument(skip_all)]
+    pub async fn list_dead_letter_webhooks(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError> {
+        self.dao
+            .get_dead_letter_webhook_events()
+            .await
+    }
+
+    /// Reset every dead-lettered webhook delivery back to retryable. Used to
+    /// recover after a merchant endpoint outage that exhausted the normal
+    /// retry budget — once the endpoint is back up, an operator replays the
+    /// backlog instead of losing those events for good.
+    #[tracing::instrument(skip_all)]
+    pub async fn replay_dead_letter_webhooks(
+        &self
+    ) -> Result<Vec<WebhookEvent>, DaoWebhookEventError> {
+        self.dao
+            .replay_dead_letter_webhook_events()
+            .await
     }
 
     pub async fn get_transaction(
@@ -723,15 +1058,13 @@ impl<D: DaoInterface> AppState<D> {
         let assets_description = self
             .asset_names_map
             .iter()
-            .map(|(asset_id, asset_name)| {
-                (
-                    asset_id.clone(),
-                    PublicAssetDescription {
-                        asset_id: asset_id.clone(),
-                        asset_name: asset_name.clone(),
-                    },
-                )
-            })
+            .map(
+                |((chain, asset_id), asset_name)| PublicAssetDescription {
+                    chain: *chain,
+                    asset_id: asset_id.clone(),
+                    asset_name: asset_name.clone(),
+                },
+            )
             .collect();
 
         KalatoriSettings {
@@ -889,8 +1222,20 @@ mod tests {
 
     async fn setup_app_state() -> AppState<MockDaoInterface> {
         let asset_names_map = HashMap::from([
-            (1337.to_string(), "USDC".to_string()),
-            (1984.to_string(), "USDt".to_string()),
+            (
+                (
+                    ChainType::PolkadotAssetHub,
+                    1337.to_string(),
+                ),
+                "USDC".to_string(),
+            ),
+            (
+                (
+                    ChainType::PolkadotAssetHub,
+                    1984.to_string(),
+                ),
+                "USDt".to_string(),
+            ),
         ]);
 
         let config = PaymentsConfig {
@@ -900,12 +1245,18 @@ mod tests {
                 1337.to_string(),
             )]),
             invoice_lifetime_millis: 600_000,
+            expiration_check_interval_millis: 10_000,
             recipient: HashMap::from([(
                 ChainType::PolkadotAssetHub,
                 "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
             )]),
             payment_url_base: "https://payments.example.com".to_string(),
             slippage_params: HashMap::new(),
+            sweep_fee_buffer: HashMap::new(),
+            recent_events_buffer_size: 256,
+            minimum_invoice_amount: HashMap::new(),
+            webhook_max_transactions: 100,
+            max_watched_invoices: None,
         };
 
         let meta = ShopMetaConfig {
@@ -926,7 +1277,7 @@ mod tests {
 
         let keyring = KeyringClient::default();
         let dao = MockDaoInterface::default();
-        let registry = InvoiceRegistry::new();
+        let registry = InvoiceRegistry::new(256);
         let swaps_executor = SwapsExecutor::default();
 
         AppState::new(
@@ -938,6 +1289,8 @@ mod tests {
             config,
             shop_config,
             SecretString::from("secret"),
+            Uuid::new_v4(),
+            ExpirationSweepCounter::new(),
         )
     }
 
@@ -1068,8 +1421,18 @@ mod tests {
             cart: InvoiceCart::empty(),
             redirect_url: "https://redirect.url".to_string(),
             include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: false,
         };
 
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning(|_| Ok(None));
+
         app_state
             .keyring
             .expect_generate_asset_hub_address()
@@ -1090,7 +1453,10 @@ mod tests {
                 asset_id: 1337.to_string(),
                 asset_name: "USDC".to_string(),
                 chain: ChainType::PolkadotAssetHub,
-                payment_address: to_base58_string(account_id.0, 0),
+                payment_address: to_base58_string(account_id.0, POLKADOT_SS58_PREFIX),
+                metadata: None,
+                expected_sender: None,
+                test: false,
                 valid_till: Utc::now()
                     + Duration::milliseconds(
                         app_state
@@ -1165,8 +1531,18 @@ mod tests {
             cart: InvoiceCart::empty(),
             redirect_url: "https://redirect.url".to_string(),
             include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: false,
         };
 
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning(|_| Ok(None));
+
         app_state
             .keyring
             .expect_generate_asset_hub_address()
@@ -1204,6 +1580,9 @@ mod tests {
             cart: InvoiceCart::empty(),
             redirect_url: "https://redirect.url".to_string(),
             include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: false,
         };
 
         let expected_create_invoice_data = {
@@ -1216,7 +1595,10 @@ mod tests {
                 asset_id: 1337.to_string(),
                 asset_name: "USDC".to_string(),
                 chain: ChainType::PolkadotAssetHub,
-                payment_address: to_base58_string(account_id.0, 0),
+                payment_address: to_base58_string(account_id.0, POLKADOT_SS58_PREFIX),
+                metadata: None,
+                expected_sender: None,
+                test: false,
                 valid_till: Utc::now()
                     + Duration::milliseconds(
                         app_state
@@ -1226,6 +1608,13 @@ mod tests {
             }
         };
 
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning(|_| Ok(None));
+
         app_state
             .keyring
             .expect_generate_asset_hub_address()
@@ -1262,4 +1651,514 @@ mod tests {
             .await;
         assert_eq!(registry_records_count, 1); // Only the first successful invoice is present
     }
+
+    #[tokio::test]
+    async fn test_create_invoice_idempotent_retry() {
+        let mut app_state = setup_app_state().await;
+
+        let params = CreateInvoiceParams {
+            order_id: "order123".to_string(),
+            amount: Decimal::new(1000, 2), // 10.00
+            cart: InvoiceCart::empty(),
+            redirect_url: "https://redirect.url".to_string(),
+            include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: false,
+        };
+
+        // Test case 1: Matching retry returns the existing invoice without
+        // touching the keyring or inserting a new row.
+        let existing_invoice = Invoice {
+            order_id: params.order_id.clone(),
+            amount: params.amount,
+            cart: params.cart.clone(),
+            ..default_invoice()
+        }
+        .with_amount(Decimal::ZERO);
+
+        let returning_invoice = existing_invoice.clone();
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning({
+                let invoice = existing_invoice.invoice.clone();
+                move |_| Ok(Some(invoice.clone()))
+            });
+
+        app_state
+            .dao
+            .expect_get_invoice_with_received_amount_by_id()
+            .once()
+            .with(eq(existing_invoice.invoice.id))
+            .returning(move |_| Ok(Some(returning_invoice.clone())));
+
+        let result = app_state
+            .create_invoice(params.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(result, existing_invoice);
+
+        // Test case 2: Retry with a different amount for the same order_id
+        // is a genuine conflict, not a retry.
+        let conflicting_params = CreateInvoiceParams {
+            amount: Decimal::new(2000, 2), // 20.00
+            ..params.clone()
+        };
+
+        let existing_invoice_for_case_2 = existing_invoice.invoice.clone();
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning(move |_| {
+                Ok(Some(
+                    existing_invoice_for_case_2.clone(),
+                ))
+            });
+
+        let result = app_state
+            .create_invoice(conflicting_params)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DaoInvoiceError::DuplicateOrderId { order_id }) if order_id == params.order_id
+        ));
+
+        // Test case 3: Retry with different metadata for the same order_id
+        // is also a genuine conflict, not a retry - otherwise the new
+        // metadata would be silently discarded and the stale value returned.
+        let conflicting_metadata_params = CreateInvoiceParams {
+            metadata: Some(serde_json::json!({"note": "retry"})),
+            ..params.clone()
+        };
+
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning(move |_| Ok(Some(existing_invoice.invoice.clone())));
+
+        let result = app_state
+            .create_invoice(conflicting_metadata_params)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DaoInvoiceError::DuplicateOrderId { order_id }) if order_id == params.order_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_invoice_rejects_non_positive_amount() {
+        let app_state = setup_app_state().await;
+
+        for amount in [Decimal::ZERO, Decimal::NEGATIVE_ONE] {
+            let params = CreateInvoiceParams {
+                order_id: "order123".to_string(),
+                amount,
+                cart: InvoiceCart::empty(),
+                redirect_url: "https://redirect.url".to_string(),
+                include_transactions: false,
+                metadata: None,
+                expected_sender: None,
+                test: false,
+            };
+
+            let result = app_state.create_invoice(params).await;
+
+            assert!(matches!(
+                result,
+                Err(DaoInvoiceError::InvalidAmount { amount: got }) if got == amount
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_invoice_rejects_amount_below_configured_minimum() {
+        let mut app_state = setup_app_state().await;
+        app_state
+            .payments_config
+            .minimum_invoice_amount
+            .insert(
+                ChainType::PolkadotAssetHub,
+                HashMap::from([(1337.to_string(), Decimal::new(1000, 2))]), // 10.00
+            );
+
+        // Below the minimum: rejected before any address is generated
+        let params = CreateInvoiceParams {
+            order_id: "order-below".to_string(),
+            amount: Decimal::new(999, 2), // 9.99
+            cart: InvoiceCart::empty(),
+            redirect_url: "https://redirect.url".to_string(),
+            include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: false,
+        };
+
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning(|_| Ok(None));
+
+        let result = app_state.create_invoice(params).await;
+
+        assert!(matches!(
+            result,
+            Err(DaoInvoiceError::AmountBelowMinimum { amount, minimum })
+                if amount == Decimal::new(999, 2) && minimum == Decimal::new(1000, 2)
+        ));
+
+        // At and above the minimum: passes the check and proceeds to generate
+        // an address (the keyring error below just proves we got that far).
+        for (order_id, amount) in [
+            ("order-at", Decimal::new(1000, 2)),    // 10.00
+            ("order-above", Decimal::new(1001, 2)), // 10.01
+        ] {
+            let params = CreateInvoiceParams {
+                order_id: order_id.to_string(),
+                amount,
+                cart: InvoiceCart::empty(),
+                redirect_url: "https://redirect.url".to_string(),
+                include_transactions: false,
+                metadata: None,
+                expected_sender: None,
+                test: false,
+            };
+
+            app_state
+                .dao
+                .expect_get_invoice_by_order_id()
+                .once()
+                .with(eq(params.order_id.clone()))
+                .returning(|_| Ok(None));
+
+            app_state
+                .keyring
+                .expect_generate_asset_hub_address()
+                .once()
+                .returning(|_| Err(KeyringError::InvalidSeed));
+
+            let result = app_state.create_invoice(params).await;
+
+            assert!(matches!(
+                result,
+                Err(DaoInvoiceError::DatabaseError)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_invoice_rejects_unknown_asset() {
+        let mut app_state = setup_app_state().await;
+        app_state
+            .payments_config
+            .default_asset_id
+            .insert(
+                ChainType::PolkadotAssetHub,
+                "9999".to_string(),
+            );
+
+        let params = CreateInvoiceParams {
+            order_id: "order-bogus-asset".to_string(),
+            amount: Decimal::new(1000, 2), // 10.00
+            cart: InvoiceCart::empty(),
+            redirect_url: "https://redirect.url".to_string(),
+            include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: false,
+        };
+
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning(|_| Ok(None));
+
+        let result = app_state.create_invoice(params).await;
+
+        assert!(matches!(
+            result,
+            Err(DaoInvoiceError::UnknownAsset { chain, asset_id })
+                if chain == ChainType::PolkadotAssetHub && asset_id == "9999"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_invoice_rejects_when_watched_invoice_cap_is_reached() {
+        let mut app_state = setup_app_state().await;
+        app_state
+            .payments_config
+            .max_watched_invoices = Some(0);
+
+        let params = CreateInvoiceParams {
+            order_id: "order-over-cap".to_string(),
+            amount: Decimal::new(1000, 2), // 10.00
+            cart: InvoiceCart::empty(),
+            redirect_url: "https://redirect.url".to_string(),
+            include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: false,
+        };
+
+        // The cap is only checked on the "create new" path, after the
+        // idempotent order_id lookup, so that lookup still has to run first.
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .with(eq(params.order_id.clone()))
+            .returning(|_| Ok(None));
+
+        let result = app_state.create_invoice(params).await;
+
+        assert!(matches!(
+            result,
+            Err(DaoInvoiceError::CapacityExceeded {
+                max: 0
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_invoice_idempotent_retry_ignores_watched_invoice_cap() {
+        let mut app_state = setup_app_state().await;
+        app_state
+            .payments_config
+            .max_watched_invoices = Some(0);
+
+        let params = CreateInvoiceParams {
+            order_id: "order-over-cap".to_string(),
+            amount: Decimal::new(1000, 2), // 10.00
+            cart: InvoiceCart::empty(),
+            redirect_url: "https://redirect.url".to_string(),
+            include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: false,
+        };
+
+        // A retry for an order_id that already has an invoice must still
+        // succeed even with the cap reached, since no new tracked invoice is
+        // created.
+        let existing_invoice = Invoice {
+            order_id: params.order_id.clone(),
+            amount: params.amount,
+            cart: params.cart.clone(),
+            ..default_invoice()
+        }
+        .with_amount(Decimal::ZERO);
+
+        let returning_invoice = existing_invoice.clone();
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning({
+                let invoice = existing_invoice.invoice.clone();
+                move |_| Ok(Some(invoice.clone()))
+            });
+
+        app_state
+            .dao
+            .expect_get_invoice_with_received_amount_by_id()
+            .once()
+            .with(eq(existing_invoice.invoice.id))
+            .returning(move |_| Ok(Some(returning_invoice.clone())));
+
+        let result = app_state
+            .create_invoice(params)
+            .await
+            .unwrap();
+
+        assert_eq!(result, existing_invoice);
+    }
+
+    #[tokio::test]
+    async fn test_create_invoice_threads_test_flag_to_created_invoice() {
+        let mut app_state = setup_app_state().await;
+
+        let uri = subxt_signer::SecretUri::from_str("//Bob").unwrap();
+        let keypair = subxt_signer::sr25519::Keypair::from_uri(&uri).unwrap();
+        let account_id = keypair.public_key().to_account_id();
+
+        let params = CreateInvoiceParams {
+            order_id: "order-test-flag".to_string(),
+            amount: Decimal::new(1000, 2), // 10.00
+            cart: InvoiceCart::empty(),
+            redirect_url: "https://redirect.url".to_string(),
+            include_transactions: false,
+            metadata: None,
+            expected_sender: None,
+            test: true,
+        };
+
+        app_state
+            .dao
+            .expect_get_invoice_by_order_id()
+            .once()
+            .with(eq(params.order_id.clone()))
+            .returning(|_| Ok(None));
+
+        app_state
+            .keyring
+            .expect_generate_asset_hub_address()
+            .once()
+            .returning(move |_| Ok(account_id.clone()));
+
+        let mut dao_transaction = MockDaoTransactionInterface::default();
+
+        dao_transaction
+            .expect_create_invoice()
+            .once()
+            .withf(|data| data.test)
+            .returning(|data| Ok(data.into()));
+
+        dao_transaction
+            .expect_create_webhook_event()
+            .once()
+            .returning(Ok);
+
+        dao_transaction
+            .expect_commit()
+            .once()
+            .returning(|| Ok(()));
+
+        app_state
+            .dao
+            .expect_begin_transaction()
+            .once()
+            .return_once(move || Ok(dao_transaction));
+
+        let result = app_state
+            .create_invoice(params)
+            .await
+            .unwrap();
+
+        assert!(result.invoice.test);
+    }
+
+    #[tokio::test]
+    async fn test_update_invoice_rejects_non_positive_amount() {
+        let app_state = setup_app_state().await;
+
+        for amount in [Decimal::ZERO, Decimal::NEGATIVE_ONE] {
+            let params = UpdateInvoiceParams {
+                invoice_id: Uuid::new_v4(),
+                amount,
+                cart: InvoiceCart::empty(),
+                include_transactions: false,
+            };
+
+            let result = app_state.update_invoice(params).await;
+
+            assert!(matches!(
+                result,
+                Err(DaoInvoiceError::InvalidAmount { amount: got }) if got == amount
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_initiate_payout_rejects_when_active_payout_exists() {
+        let mut app_state = setup_app_state().await;
+        let invoice_id = Uuid::new_v4();
+
+        let invoice = Invoice {
+            id: invoice_id,
+            status: InvoiceStatus::Paid,
+            ..default_invoice()
+        };
+
+        app_state
+            .dao
+            .expect_get_invoice_by_id()
+            .once()
+            .with(eq(invoice_id))
+            .returning(move |_| Ok(Some(invoice.clone())));
+
+        let mut dao_transaction = MockDaoTransactionInterface::default();
+
+        dao_transaction
+            .expect_count_payouts()
+            .once()
+            .returning(|_| Ok(1));
+
+        app_state
+            .dao
+            .expect_begin_transaction()
+            .once()
+            .return_once(move || Ok(dao_transaction));
+
+        let result = app_state
+            .initiate_payout(invoice_id)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(DaoInvoiceError::PayoutAlreadyInProgress { invoice_id: id }) if id == invoice_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_initiate_payout_creates_payout_when_none_active() {
+        let mut app_state = setup_app_state().await;
+        let invoice_id = Uuid::new_v4();
+
+        let invoice = Invoice {
+            id: invoice_id,
+            status: InvoiceStatus::Paid,
+            chain: ChainType::PolkadotAssetHub,
+            ..default_invoice()
+        };
+
+        app_state
+            .dao
+            .expect_get_invoice_by_id()
+            .once()
+            .with(eq(invoice_id))
+            .returning(move |_| Ok(Some(invoice.clone())));
+
+        let mut dao_transaction = MockDaoTransactionInterface::default();
+
+        dao_transaction
+            .expect_count_payouts()
+            .once()
+            .returning(|_| Ok(0));
+
+        dao_transaction
+            .expect_create_payout()
+            .once()
+            .returning(|payout| Ok(payout));
+
+        dao_transaction
+            .expect_commit()
+            .once()
+            .returning(|| Ok(()));
+
+        app_state
+            .dao
+            .expect_begin_transaction()
+            .once()
+            .return_once(move || Ok(dao_transaction));
+
+        let result = app_state
+            .initiate_payout(invoice_id)
+            .await
+            .unwrap();
+
+        assert_eq!(result.invoice_id, invoice_id);
+    }
 }
@@ -1,8 +1,15 @@
 use std::collections::HashMap;
 
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
-use crate::dao::DaoInterface;
+use kalatori_client::types::ChainType;
+
+use crate::chain::ReapError;
+use crate::dao::{
+    DaoInterface,
+    DaoInvoiceError,
+};
 use crate::types::InvoiceWithReceivedAmount;
 
 use super::AppState;
@@ -11,4 +18,63 @@ impl<D: DaoInterface> AppState<D> {
     pub async fn get_invoices_registry_state(&self) -> HashMap<Uuid, InvoiceWithReceivedAmount> {
         self.registry.state().await
     }
+
+    /// Force the tracker to stop watching an invoice's address, without
+    /// touching its database status. Useful to unstick a stuck invoice
+    /// during debugging without waiting for it to expire naturally.
+    /// Errors with `NotFound` if the invoice wasn't being tracked, so the
+    /// caller can tell "untracked" apart from "nothing to do".
+    pub async fn force_untrack_invoice(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<(), DaoInvoiceError> {
+        self.registry
+            .remove_invoice(&invoice_id)
+            .await
+            .map(|_| ())
+            .ok_or(DaoInvoiceError::NotFound {
+                invoice_id,
+            })
+    }
+
+    /// Stop tracking an invoice, but only once it's settled. Refuses with
+    /// [`ReapError::StillActive`] if the invoice still has pending expected
+    /// funds, unlike [`AppState::force_untrack_invoice`].
+    pub async fn reap_invoice(
+        &self,
+        invoice_id: Uuid,
+    ) -> Result<(), ReapError> {
+        self.registry
+            .reap_invoice(&invoice_id)
+            .await
+            .map(|_| ())
+    }
+
+    /// Look up a single tracked invoice by its payment address, without
+    /// dumping the whole registry. Useful when an order seems stuck and we
+    /// need to confirm whether the watcher is actually tracking its address.
+    pub async fn get_invoice_by_registry_address(
+        &self,
+        chain: ChainType,
+        asset_id: &str,
+        address: &str,
+    ) -> Option<InvoiceWithReceivedAmount> {
+        self.registry
+            .find_invoice_by_address(address, chain, asset_id)
+            .await
+    }
+
+    /// Look up an asset by its human-readable symbol (e.g. `"USDC"`),
+    /// returning `(asset_id, decimals, min_balance)`. Useful when debugging
+    /// a config or a merchant-reported issue from a symbol rather than a
+    /// raw asset ID.
+    pub async fn get_asset_info_by_name(
+        &self,
+        chain: ChainType,
+        name: &str,
+    ) -> Option<(String, u8, Decimal)> {
+        self.balance_checker
+            .get_asset_info_by_name(chain, name)
+            .await
+    }
 }
@@ -193,9 +193,10 @@ pub struct ListTransactionsParams {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublicAssetDescription {
+    pub chain: ChainType,
     pub asset_id: String,
     pub asset_name: String,
-    // TODO: add asset decimals and specify chain
+    // TODO: add asset decimals
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -209,7 +210,7 @@ pub struct KalatoriSettings {
     pub default_asset_id: HashMap<ChainType, String>,
     pub payment_url_base: String,
     pub slippage_params: HashMap<ChainType, HashMap<String, SlippageParams>>,
-    pub assets_description: HashMap<String, PublicAssetDescription>,
+    pub assets_description: Vec<PublicAssetDescription>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
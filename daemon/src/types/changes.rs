@@ -418,7 +418,7 @@ where
 
 /// Parse a hex-encoded UUID string to Uuid.
 fn parse_hex_uuid(hex: &str) -> Result<Uuid, String> {
-    let bytes = const_hex::decode(hex).map_err(|e| format!("Invalid hex string: {e}"))?;
+    let bytes = crate::utils::hex::unhex(hex).map_err(|e| format!("Invalid hex string: {e}"))?;
 
     Uuid::from_slice(&bytes).map_err(|e| format!("Invalid UUID bytes: {e}"))
 }
@@ -18,9 +18,74 @@ use sqlx::{
     FromRow,
     Type,
 };
+use uuid::Uuid;
 
 pub use kalatori_client::types::ChainType;
 
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Identifies the running daemon instance, for `/public/health` and operators
+/// correlating webhook deliveries back to a specific process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub version: &'static str,
+    /// Loaded at startup from the `instance_id` file in the database
+    /// directory, or generated and persisted there if absent (see
+    /// `utils::instance_id::load_or_create`), so it survives restarts.
+    pub instance_id: Uuid,
+}
+
+impl ServerInfo {
+    pub fn new(instance_id: Uuid) -> Self {
+        Self {
+            version: VERSION,
+            instance_id,
+        }
+    }
+}
+
+/// Introspection snapshot of
+/// [`crate::expiration_detector::ExpirationDetector`]'s last completed sweep,
+/// for monitoring whether expired invoices are being reaped in a timely manner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpirationSweepStats {
+    /// Number of invoices reaped by the most recently completed sweep (0 if
+    /// none have run yet, or the last sweep found nothing to reap).
+    pub last_reaped_count: u32,
+}
+
+/// One entry of [`crate::chain::InvoiceRegistry`]'s bounded recent-events
+/// log, for the `/internal/recent-events` post-mortem debugging endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicRecentEvent {
+    pub timestamp: DateTime<Utc>,
+    pub kind: &'static str,
+    pub invoice_id: Uuid,
+}
+
+/// The last block a chain client's watcher has actually ingested, for the
+/// `/internal/chain-tip` monitoring endpoint. Reflects what the watcher has
+/// processed rather than the RPC endpoint's current head, since that's what
+/// determines invoice evaluation latency.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicChainTip {
+    pub chain: ChainType,
+    /// `None` if the watcher hasn't processed a block for this chain yet.
+    pub block_number: Option<u32>,
+    pub block_hash: Option<String>,
+    /// Milliseconds since epoch, as recorded in `ChainTip::timestamp` (chain
+    /// clock on Asset Hub, local wall time on Polygon).
+    pub timestamp: Option<u64>,
+    /// EMA of this chain's inter-block duration, in milliseconds. `None`
+    /// until the watcher has processed at least two blocks.
+    pub block_time_estimate_millis: Option<u64>,
+    /// The runtime `spec_version` the watcher negotiated at connection time,
+    /// for confirming it's talking to the chain version its baked-in
+    /// metadata was generated from. `None` on chains with no comparable
+    /// runtime-version concept (Polygon).
+    pub spec_version: Option<u32>,
+}
+
 /// Initiator type for payouts and refunds
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, Display, EnumString)]
 #[strum(crate = "kalatori_client::strum")]
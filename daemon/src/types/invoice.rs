@@ -42,9 +42,27 @@ pub struct Invoice {
     pub status: InvoiceStatus,
     pub cart: InvoiceCart,
     pub redirect_url: String,
+    // Opaque merchant-provided data, echoed back verbatim in the public
+    // invoice and its webhook/event payloads. Size-limited at creation time
+    // (see `MAX_METADATA_SIZE_BYTES` in `state.rs`).
+    pub metadata: Option<serde_json::Value>,
+    // Merchant-configured sender restriction. When set, incoming transfers
+    // from any other address are recorded but don't count toward
+    // `total_received_amount` (see `TransactionsRecorder::process_invoice_transaction`).
+    pub expected_sender: Option<String>,
+    // Marks a test order. Carries no special handling on the daemon side —
+    // it's tracked, paid, and expired exactly like any other invoice — and
+    // exists purely so merchants can echo it through to their own webhook
+    // handler and skip real fulfillment for it.
+    pub test: bool,
     pub valid_till: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    // When the received balance first satisfied `amount`, independent of
+    // `status`. Used to fire the `Seen` webhook event exactly once, even if
+    // a reorg later reverts `status` back to Waiting/PartiallyPaid and it
+    // returns to Paid again.
+    pub seen_at: Option<DateTime<Utc>>,
 }
 
 impl Invoice {
@@ -57,6 +75,17 @@ impl Invoice {
             total_received_amount,
         }
     }
+
+    /// Whether the invoice's `valid_till` has passed as of `now`. Centralizes
+    /// the boundary semantics (`now == valid_till` is not yet expired) so
+    /// status-reporting code agrees with `DAO::get_expired_invoices`, which
+    /// reaps invoices once `valid_till` is strictly in the past.
+    pub fn is_expired(
+        &self,
+        now: DateTime<Utc>,
+    ) -> bool {
+        now > self.valid_till
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -86,11 +115,15 @@ impl InvoiceWithReceivedAmount {
             ),
             redirect_url: self.invoice.redirect_url,
             cart: self.invoice.cart,
+            metadata: self.invoice.metadata,
+            expected_sender: self.invoice.expected_sender,
+            test: self.invoice.test,
             valid_till: self.invoice.valid_till,
             created_at: self.invoice.created_at,
             updated_at: self.invoice.updated_at,
             total_received_amount: self.total_received_amount,
             transactions: vec![],
+            transactions_truncated: false,
         }
     }
 
@@ -112,9 +145,13 @@ pub struct InvoiceRow {
     pub status: InvoiceStatus,
     pub cart: Json<InvoiceCart>,
     pub redirect_url: String,
+    pub metadata: Option<Json<serde_json::Value>>,
+    pub expected_sender: Option<String>,
+    pub test: bool,
     pub valid_till: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub seen_at: Option<DateTime<Utc>>,
 }
 
 impl From<InvoiceRow> for Invoice {
@@ -130,9 +167,13 @@ impl From<InvoiceRow> for Invoice {
             status: row.status,
             cart: row.cart.0,
             redirect_url: row.redirect_url,
+            metadata: row.metadata.map(Json::into_inner),
+            expected_sender: row.expected_sender,
+            test: row.test,
             valid_till: row.valid_till,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            seen_at: row.seen_at,
         }
     }
 }
@@ -148,6 +189,9 @@ pub struct CreateInvoiceData {
     pub payment_address: String,
     pub cart: InvoiceCart,
     pub redirect_url: String,
+    pub metadata: Option<serde_json::Value>,
+    pub expected_sender: Option<String>,
+    pub test: bool,
     pub valid_till: DateTime<Utc>,
 }
 
@@ -166,9 +210,13 @@ impl From<CreateInvoiceData> for Invoice {
             status: InvoiceStatus::Waiting,
             cart: data.cart,
             redirect_url: data.redirect_url,
+            metadata: data.metadata,
+            expected_sender: data.expected_sender,
+            test: data.test,
             valid_till: data.valid_till,
             created_at: now,
             updated_at: now,
+            seen_at: None,
         }
     }
 }
@@ -201,6 +249,9 @@ pub fn default_create_invoice_data() -> CreateInvoiceData {
         payment_address: "0x45f077823C8d036a1a9f7Cd28e86Bd98191dF2b7".to_string(),
         cart: InvoiceCart::empty(),
         redirect_url: "http://localhost:8080/thankyou".to_string(),
+        metadata: None,
+        expected_sender: None,
+        test: false,
         #[expect(clippy::arithmetic_side_effects)]
         valid_till: now + chrono::Duration::hours(24),
     }
@@ -218,3 +269,38 @@ pub fn default_update_invoice_data(invoice_id: Uuid) -> UpdateInvoiceData {
         valid_till: now + chrono::Duration::hours(24),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_expired_is_false_before_valid_till() {
+        let mut invoice = default_invoice();
+        invoice.valid_till = Utc::now();
+
+        #[expect(clippy::arithmetic_side_effects)]
+        let before = invoice.valid_till - chrono::Duration::seconds(1);
+
+        assert!(!invoice.is_expired(before));
+    }
+
+    #[test]
+    fn is_expired_is_false_exactly_at_valid_till() {
+        let mut invoice = default_invoice();
+        invoice.valid_till = Utc::now();
+
+        assert!(!invoice.is_expired(invoice.valid_till));
+    }
+
+    #[test]
+    fn is_expired_is_true_after_valid_till() {
+        let mut invoice = default_invoice();
+        invoice.valid_till = Utc::now();
+
+        #[expect(clippy::arithmetic_side_effects)]
+        let after = invoice.valid_till + chrono::Duration::seconds(1);
+
+        assert!(invoice.is_expired(after));
+    }
+}
@@ -2,6 +2,7 @@ use chrono::{
     DateTime,
     Utc,
 };
+use serde::Serialize;
 use uuid::Uuid;
 
 pub use kalatori_client::types::{
@@ -10,14 +11,25 @@ pub use kalatori_client::types::{
     KalatoriEventExt,
 };
 
-#[derive(Debug, sqlx::FromRow)]
+use super::common::RetryMeta;
+
+#[derive(Debug, sqlx::FromRow, Serialize)]
 pub struct WebhookEvent {
     pub id: Uuid,
     pub entity_id: Uuid,
     pub payload: serde_json::Value,
     pub sent: bool,
+    /// Set when delivery gave up after a non-retriable failure, rather than
+    /// actually reaching the merchant's endpoint. `sent` is also set in that
+    /// case (see `record_webhook_event_failure`), so this is what
+    /// distinguishes the two for `get_dead_letter_webhook_events`/
+    /// `replay_dead_letter_webhook_events`.
+    pub dead_letter: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub retry_meta: RetryMeta,
 }
 
 impl<T: KalatoriEventExt> From<GenericEvent<T>> for WebhookEvent {
@@ -30,8 +42,10 @@ impl<T: KalatoriEventExt> From<GenericEvent<T>> for WebhookEvent {
             entity_id: event.payload.entity_id(),
             payload,
             sent: false,
+            dead_letter: false,
             created_at: event.timestamp,
             updated_at: event.timestamp,
+            retry_meta: RetryMeta::default(),
         }
     }
 }
@@ -53,11 +67,15 @@ pub fn default_webhook_event(invoice_id: Uuid) -> GenericEvent<super::PublicInvo
         cart: kalatori_client::types::InvoiceCart {
             items: vec![],
         },
+        metadata: None,
+        expected_sender: None,
+        test: false,
         valid_till: Utc::now() + chrono::Duration::hours(24),
         created_at: Utc::now(),
         updated_at: Utc::now(),
         total_received_amount: rust_decimal::Decimal::ZERO,
         transactions: vec![],
+        transactions_truncated: false,
     };
 
     invoice.build_event(InvoiceEventType::Created)
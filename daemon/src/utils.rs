@@ -1,3 +1,5 @@
+pub mod hex;
+pub mod instance_id;
 pub mod logger;
 pub mod logging;
 mod refund_destination_detector;
@@ -0,0 +1,79 @@
+//! Lenient hex-string decoding for values that cross RPC node boundaries,
+//! where `0x`/`0X` prefixing and digit case vary between implementations.
+//!
+//! This stays a plain function rather than a `FromStr`/`TryFrom` impl on a
+//! owned hash type: block and transaction hashes in this codebase are the
+//! chain libraries' own types (`subxt`'s `H256`, `alloy`'s `B256`), which
+//! already implement those traits upstream, so there's no local newtype to
+//! hang a conversion off without duplicating what the libraries provide.
+
+use thiserror::Error;
+
+/// A string failed to decode as hex, either because it contains non-hex
+/// characters or has an odd number of hex digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("input is not valid hexadecimal")]
+pub struct NotHexError;
+
+/// Decode a hex string into bytes, tolerating an optional `0x`/`0X` prefix
+/// and either digit case.
+pub fn unhex(input: &str) -> Result<Vec<u8>, NotHexError> {
+    let trimmed = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .unwrap_or(input);
+
+    const_hex::decode(trimmed).map_err(|_e| NotHexError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_lowercase_prefix() {
+        assert_eq!(
+            unhex("0xdeadbeef").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+    }
+
+    #[test]
+    fn accepts_uppercase_prefix() {
+        assert_eq!(
+            unhex("0XDEADBEEF").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+    }
+
+    #[test]
+    fn accepts_unprefixed() {
+        assert_eq!(
+            unhex("deadbeef").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+    }
+
+    #[test]
+    fn accepts_mixed_case() {
+        assert_eq!(
+            unhex("DeAdBeEf").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+    }
+
+    #[test]
+    fn rejects_odd_length() {
+        assert_eq!(unhex("0xabc"), Err(NotHexError));
+    }
+
+    #[test]
+    fn rejects_non_hex_characters() {
+        assert_eq!(unhex("0xzzzz"), Err(NotHexError));
+    }
+
+    #[test]
+    fn accepts_bare_prefix_as_empty() {
+        assert_eq!(unhex("0x").unwrap(), Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,43 @@
+//! Best-effort persistence of a stable identifier for this daemon's
+//! deployment, so a restart keeps reporting the same `instance_id` (see
+//! [`crate::types::ServerInfo`]) instead of looking like a freshly replaced
+//! instance to merchants watching webhook deliveries.
+
+use std::path::Path;
+
+use uuid::Uuid;
+
+const INSTANCE_ID_FILE_NAME: &str = "instance_id";
+
+/// Read the instance id from `<dir>/instance_id`, creating and persisting a
+/// fresh one if it's missing, unreadable, or not a valid UUID. Any I/O
+/// failure falls back to a fresh in-memory id with a warning: a working
+/// daemon matters more than a stable id.
+pub fn load_or_create(dir: &Path) -> Uuid {
+    let path = dir.join(INSTANCE_ID_FILE_NAME);
+
+    if let Ok(contents) = std::fs::read_to_string(&path) {
+        match contents.trim().parse() {
+            Ok(id) => return id,
+            Err(e) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error.source = ?e,
+                    "Instance id file doesn't contain a valid UUID, generating a new one"
+                );
+            },
+        }
+    }
+
+    let id = Uuid::new_v4();
+
+    if let Err(e) = std::fs::write(&path, id.to_string()) {
+        tracing::warn!(
+            path = %path.display(),
+            error.source = ?e,
+            "Failed to persist instance id, it won't survive a restart"
+        );
+    }
+
+    id
+}
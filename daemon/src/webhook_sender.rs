@@ -18,16 +18,32 @@ use kalatori_client::utils::{
 };
 
 use crate::dao::DaoInterface;
-use crate::types::WebhookEvent;
+use crate::types::{
+    RetryMeta,
+    WebhookEvent,
+};
 
 const WEBHOOK_SENDER_INTERVAL_MILLIS: u64 = 100;
-const WEBHOOK_SENDER_MAX_CONCURRENT_REQUESTS: usize = 10;
-const WEBHOOK_SENDER_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+/// Upper bound on how long shutdown waits for in-flight deliveries to finish
+/// once a shutdown signal is received, so a restart can't block forever.
+const WEBHOOK_SENDER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(90);
+
+#[derive(Debug, PartialEq, Eq)]
+enum SendWebhookOutcome {
+    Ok,
+    /// Failed, but worth retrying later (network error, timeout, 408/429, 5xx).
+    Retriable,
+    /// Failed in a way that won't change on retry (e.g. 4xx other than
+    /// 408/429), so delivery is given up on.
+    Permanent,
+}
 
 #[derive(Debug, PartialEq, Eq)]
 struct SendWebhookResult {
     event_id: Uuid,
-    is_ok: bool,
+    retry_meta: RetryMeta,
+    outcome: SendWebhookOutcome,
+    failure_message: String,
 }
 
 #[tracing::instrument(skip(client, request))]
@@ -35,6 +51,7 @@ async fn send_webhook(
     client: reqwest::Client,
     request: reqwest::Request,
     event_id: Uuid,
+    retry_meta: RetryMeta,
 ) -> SendWebhookResult {
     match client.execute(request).await {
         Ok(response) if response.status().is_success() => {
@@ -45,22 +62,34 @@ async fn send_webhook(
 
             SendWebhookResult {
                 event_id,
-                is_ok: true,
+                retry_meta,
+                outcome: SendWebhookOutcome::Ok,
+                failure_message: String::new(),
             }
         },
         Ok(response) => {
             let status = response.status();
+            let is_retriable = status.is_server_error()
+                || status == reqwest::StatusCode::REQUEST_TIMEOUT
+                || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
             let response_text = response.text().await;
 
             tracing::warn!(
                 event_id = %event_id,
                 response.status = %status,
                 response.text = ?response_text,
+                is_retriable,
                 "Failed to send webhook event, non-success status code received",
             );
             SendWebhookResult {
                 event_id,
-                is_ok: false,
+                retry_meta,
+                outcome: if is_retriable {
+                    SendWebhookOutcome::Retriable
+                } else {
+                    SendWebhookOutcome::Permanent
+                },
+                failure_message: format!("HTTP {status}"),
             }
         },
         Err(e) => {
@@ -72,7 +101,9 @@ async fn send_webhook(
 
             SendWebhookResult {
                 event_id,
-                is_ok: false,
+                retry_meta,
+                outcome: SendWebhookOutcome::Retriable,
+                failure_message: e.to_string(),
             }
         },
     }
@@ -83,6 +114,12 @@ pub struct WebhookSender<D: DaoInterface + 'static> {
     dao: D,
     webhook_url: Option<String>,
     hmac_config: HmacConfig,
+    request_timeout: Duration,
+    max_concurrent_requests: usize,
+    content_type: String,
+    /// After this many failed attempts, a retriable failure is dead-lettered
+    /// instead of backing off again.
+    max_attempts: u32,
     processing_events_ids: HashSet<Uuid>,
 }
 
@@ -91,12 +128,20 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
         dao: D,
         webhook_url: Option<String>,
         hmac_config: HmacConfig,
+        request_timeout: Duration,
+        max_concurrent_requests: usize,
+        content_type: String,
+        max_attempts: u32,
     ) -> Self {
         WebhookSender {
             client: reqwest::Client::new(),
             dao,
             webhook_url,
             hmac_config,
+            request_timeout,
+            max_concurrent_requests,
+            content_type,
+            max_attempts,
             processing_events_ids: HashSet::new(),
         }
     }
@@ -105,28 +150,37 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
         &self,
         url: &str,
         event: WebhookEvent,
-    ) -> reqwest::Request {
+    ) -> Option<reqwest::Request> {
         let mut request = self
             .client
             .post(url)
             .json(&event.payload)
-            .timeout(WEBHOOK_SENDER_REQUEST_TIMEOUT)
+            .timeout(self.request_timeout)
             .build()
             // This can fail only if we have invalid URL or serialization fails.
             // So we need to check URL on startup. Don't expect serialization failures.
             .inspect_err(|e| {
                 tracing::error!(
+                    event_id = %event.id,
                     error.source = ?e,
                     "Error while building webhook event request"
                 )
             })
-            // TODO: Normally this shouldn't fail at all, but we don't check URL validity on startup
-            // for now
-            .unwrap();
+            .ok()?;
+
+        // Overrides the `Content-Type` set by `.json()` above. The body stays
+        // JSON regardless - this only changes the header merchant stacks
+        // route on.
+        if let Ok(content_type) = reqwest::header::HeaderValue::from_str(&self.content_type) {
+            request.headers_mut().insert(
+                reqwest::header::CONTENT_TYPE,
+                content_type,
+            );
+        }
 
         add_headers_to_reqwest(&self.hmac_config, &mut request);
 
-        request
+        Some(request)
     }
 
     fn build_future(
@@ -134,15 +188,25 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
         event: WebhookEvent,
     ) -> Pin<Box<dyn Future<Output = SendWebhookResult> + Send + 'static>> {
         let event_id = event.id;
+        let retry_meta = event.retry_meta.clone();
 
         if let Some(url) = self.webhook_url.as_ref() {
-            let request = self.build_request(url, event);
-
-            Box::pin(send_webhook(
-                self.client.clone(),
-                request,
-                event_id,
-            ))
+            match self.build_request(url, event) {
+                Some(request) => Box::pin(send_webhook(
+                    self.client.clone(),
+                    request,
+                    event_id,
+                    retry_meta,
+                )),
+                None => Box::pin(async move {
+                    SendWebhookResult {
+                        event_id,
+                        retry_meta,
+                        outcome: SendWebhookOutcome::Permanent,
+                        failure_message: "Failed to build webhook request".to_string(),
+                    }
+                }),
+            }
         } else {
             Box::pin(async move {
                 tracing::trace!(
@@ -152,7 +216,9 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
 
                 SendWebhookResult {
                     event_id,
-                    is_ok: true,
+                    retry_meta,
+                    outcome: SendWebhookOutcome::Ok,
+                    failure_message: String::new(),
                 }
             })
         }
@@ -161,7 +227,7 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
     async fn prepare_webhook_events(
         &mut self
     ) -> Vec<Pin<Box<dyn Future<Output = SendWebhookResult> + Send + 'static>>> {
-        let limit = WEBHOOK_SENDER_MAX_CONCURRENT_REQUESTS - self.processing_events_ids.len();
+        let limit = self.max_concurrent_requests - self.processing_events_ids.len();
 
         if limit == 0 {
             return Vec::new();
@@ -197,23 +263,53 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
         self.processing_events_ids
             .remove(&result.event_id);
 
-        if result.is_ok
-            && self
-                .dao
-                .mark_webhook_event_as_sent(result.event_id)
-                .await
-                .is_err()
-        {
-            tracing::warn!(
-                event_id = %result.event_id,
-                error.category = "webhook_sender",
-                error.operation = "handle_send_webhook_result",
-                "Failed to mark webhook event as sent in database. It might be resent"
-            )
-        };
-        // TODO: for now we do nothing on failure, the event will be retried
-        // later. Later we might want to implement some retry strategy
-        // with backoff and max attempts count
+        match result.outcome {
+            SendWebhookOutcome::Ok => {
+                if self
+                    .dao
+                    .mark_webhook_event_as_sent(result.event_id)
+                    .await
+                    .is_err()
+                {
+                    tracing::warn!(
+                        event_id = %result.event_id,
+                        error.category = "webhook_sender",
+                        error.operation = "handle_send_webhook_result",
+                        "Failed to mark webhook event as sent in database. It might be resent"
+                    )
+                };
+            },
+            SendWebhookOutcome::Retriable | SendWebhookOutcome::Permanent => {
+                let mut retry_meta = result.retry_meta;
+                retry_meta.increment_retry(result.failure_message);
+
+                // A retriable failure keeps being retried only while it
+                // hasn't used up max_attempts yet; once exhausted it's
+                // dead-lettered the same as a non-retriable one, instead of
+                // backing off forever against an endpoint that's never
+                // coming back.
+                let is_retriable = result.outcome == SendWebhookOutcome::Retriable
+                    && retry_meta.retry_count < self.max_attempts;
+
+                if self
+                    .dao
+                    .record_webhook_event_failure(
+                        result.event_id,
+                        retry_meta,
+                        is_retriable,
+                    )
+                    .await
+                    .is_err()
+                {
+                    tracing::warn!(
+                        event_id = %result.event_id,
+                        error.category = "webhook_sender",
+                        error.operation = "handle_send_webhook_result",
+                        "Failed to record webhook event delivery failure in database. It might be retried sooner than expected"
+                    )
+                };
+            },
+        }
     }
 
     async fn perform(
@@ -226,6 +322,8 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
 
         let mut shutdown_expected = false;
         let mut futures_set = FuturesUnordered::new();
+        let shutdown_deadline = tokio::time::sleep(WEBHOOK_SENDER_SHUTDOWN_TIMEOUT);
+        tokio::pin!(shutdown_deadline);
 
         loop {
             tokio::select! {
@@ -245,12 +343,15 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
                         break;
                     }
                 }
-                () = token.cancelled() => {
+                () = token.cancelled(), if !shutdown_expected => {
                     tracing::info!(
                         "Webhook sender received shutdown signal, finishing pending tasks before shutting down"
                     );
 
                     shutdown_expected = true;
+                    shutdown_deadline
+                        .as_mut()
+                        .reset(tokio::time::Instant::now() + WEBHOOK_SENDER_SHUTDOWN_TIMEOUT);
 
                     if futures_set.is_empty() {
                         tracing::info!(
@@ -260,6 +361,14 @@ impl<D: DaoInterface + 'static> WebhookSender<D> {
                         break;
                     }
                 }
+                () = &mut shutdown_deadline, if shutdown_expected => {
+                    tracing::warn!(
+                        pending_deliveries = futures_set.len(),
+                        "Shutdown timeout elapsed with webhook deliveries still in flight, shutting down anyway"
+                    );
+
+                    break;
+                }
             }
         }
     }
@@ -296,6 +405,9 @@ mod tests {
 
     use super::*;
 
+    const TEST_MAX_CONCURRENT_REQUESTS: usize = 10;
+    const TEST_MAX_ATTEMPTS: u32 = 10;
+
     fn generate_events(count: usize) -> Vec<WebhookEvent> {
         (0..count)
             .map(|_| {
@@ -328,6 +440,10 @@ mod tests {
             dao,
             Some(server.base_url()),
             hmac_config,
+            Duration::from_secs(60),
+            TEST_MAX_CONCURRENT_REQUESTS,
+            "application/json".to_string(),
+            TEST_MAX_ATTEMPTS,
         );
 
         let mut events = generate_events(2);
@@ -336,17 +452,54 @@ mod tests {
         let event_2_id = event_2.id;
 
         let result = sender.build_future(event_1).await;
-        assert!(result.is_ok);
+        assert_eq!(result.outcome, SendWebhookOutcome::Ok);
         assert_eq!(result.event_id, event_1_id);
         ok_mock.assert_calls(1);
 
         sender.webhook_url = None;
         let result = sender.build_future(event_2).await;
-        assert!(result.is_ok);
+        assert_eq!(result.outcome, SendWebhookOutcome::Ok);
         assert_eq!(result.event_id, event_2_id);
         ok_mock.assert_calls(1);
     }
 
+    #[tokio::test]
+    async fn test_build_future_request_timeout_is_retriable() {
+        let server = MockServer::start();
+
+        let slow_mock = server.mock(|when, then| {
+            when.method(POST);
+
+            then.status(200)
+                .delay(Duration::from_millis(200));
+        });
+
+        let dao = MockDaoInterface::default();
+        let hmac_config = HmacConfig::new(b"test".to_vec(), 10);
+
+        let sender = WebhookSender::new(
+            dao,
+            Some(server.base_url()),
+            hmac_config,
+            Duration::from_millis(50),
+            TEST_MAX_CONCURRENT_REQUESTS,
+            "application/json".to_string(),
+            TEST_MAX_ATTEMPTS,
+        );
+
+        let mut events = generate_events(1);
+        let event = events.remove(0);
+        let event_id = event.id;
+
+        let result = sender.build_future(event).await;
+        assert_eq!(
+            result.outcome,
+            SendWebhookOutcome::Retriable
+        );
+        assert_eq!(result.event_id, event_id);
+        slow_mock.assert_calls(1);
+    }
+
     #[tokio::test]
     async fn test_send_webhook() {
         let server = MockServer::start();
@@ -363,26 +516,29 @@ mod tests {
             });
 
             let event_id = Uuid::new_v4();
-
-            let expected_result = SendWebhookResult {
-                event_id,
-                is_ok: true,
-            };
+            let retry_meta = RetryMeta::default();
 
             let request = client
                 .request(reqwest::Method::GET, server.base_url())
                 .build()
                 .unwrap();
 
-            let result = send_webhook(client.clone(), request, event_id).await;
+            let result = send_webhook(
+                client.clone(),
+                request,
+                event_id,
+                retry_meta,
+            )
+            .await;
 
-            assert_eq!(expected_result, result);
+            assert_eq!(result.event_id, event_id);
+            assert_eq!(result.outcome, SendWebhookOutcome::Ok);
             ok_mock.assert();
         }
 
         // Test case 2:
         // - Unsuccessful flow
-        // - Server responded with non-200
+        // - Server responded with non-retriable status code
         {
             let non_ok_mock = server.mock(|when, then| {
                 when.method(POST);
@@ -391,33 +547,69 @@ mod tests {
             });
 
             let event_id = Uuid::new_v4();
-
-            let expected_result = SendWebhookResult {
-                event_id,
-                is_ok: false,
-            };
+            let retry_meta = RetryMeta::default();
 
             let request = client
                 .request(reqwest::Method::POST, server.base_url())
                 .build()
                 .unwrap();
 
-            let result = send_webhook(client.clone(), request, event_id).await;
+            let result = send_webhook(
+                client.clone(),
+                request,
+                event_id,
+                retry_meta,
+            )
+            .await;
 
-            assert_eq!(expected_result, result);
+            assert_eq!(result.event_id, event_id);
+            assert_eq!(
+                result.outcome,
+                SendWebhookOutcome::Permanent
+            );
             non_ok_mock.assert();
         }
 
         // Test case 3:
         // - Unsuccessful flow
-        // - Invalid server (reqwest error)
+        // - Server responded with a retriable status code
         {
+            let retriable_mock = server.mock(|when, then| {
+                when.method(POST);
+
+                then.status(503);
+            });
+
             let event_id = Uuid::new_v4();
+            let retry_meta = RetryMeta::default();
 
-            let expected_result = SendWebhookResult {
+            let request = client
+                .request(reqwest::Method::POST, server.base_url())
+                .build()
+                .unwrap();
+
+            let result = send_webhook(
+                client.clone(),
+                request,
                 event_id,
-                is_ok: false,
-            };
+                retry_meta,
+            )
+            .await;
+
+            assert_eq!(result.event_id, event_id);
+            assert_eq!(
+                result.outcome,
+                SendWebhookOutcome::Retriable
+            );
+            retriable_mock.assert();
+        }
+
+        // Test case 4:
+        // - Unsuccessful flow
+        // - Invalid server (reqwest error)
+        {
+            let event_id = Uuid::new_v4();
+            let retry_meta = RetryMeta::default();
 
             let request = client
                 .request(
@@ -427,9 +619,19 @@ mod tests {
                 .build()
                 .unwrap();
 
-            let result = send_webhook(client.clone(), request, event_id).await;
+            let result = send_webhook(
+                client.clone(),
+                request,
+                event_id,
+                retry_meta,
+            )
+            .await;
 
-            assert_eq!(expected_result, result);
+            assert_eq!(result.event_id, event_id);
+            assert_eq!(
+                result.outcome,
+                SendWebhookOutcome::Retriable
+            );
         }
     }
 
@@ -442,6 +644,10 @@ mod tests {
             dao,
             Some("http://webhook.example.com".to_string()),
             hmac_config,
+            Duration::from_secs(60),
+            TEST_MAX_CONCURRENT_REQUESTS,
+            "application/json".to_string(),
+            TEST_MAX_ATTEMPTS,
         );
 
         let event: WebhookEvent = default_invoice()
@@ -452,7 +658,9 @@ mod tests {
 
         let expected_body_string = event.payload.to_string();
 
-        let result = sender.build_request("http://webhook.example.com", event);
+        let result = sender
+            .build_request("http://webhook.example.com", event)
+            .unwrap();
         assert!(matches!(
             *result.method(),
             reqwest::Method::POST
@@ -466,7 +674,7 @@ mod tests {
         assert!(result.timeout().is_some());
         assert_eq!(
             *result.timeout().unwrap(),
-            WEBHOOK_SENDER_REQUEST_TIMEOUT
+            Duration::from_secs(60)
         );
         assert!(result.body().is_some());
         assert_eq!(
@@ -485,6 +693,68 @@ mod tests {
         assert!(result_headers.contains_key(SIGNATURE_HEADER));
     }
 
+    #[tokio::test]
+    async fn test_build_request_uses_configured_content_type() {
+        let dao = MockDaoInterface::default();
+        let hmac_config = HmacConfig::new(b"test".to_vec(), 10);
+
+        let sender = WebhookSender::new(
+            dao,
+            Some("http://webhook.example.com".to_string()),
+            hmac_config,
+            Duration::from_secs(60),
+            TEST_MAX_CONCURRENT_REQUESTS,
+            "application/vnd.example.webhook+json".to_string(),
+            TEST_MAX_ATTEMPTS,
+        );
+
+        let event: WebhookEvent = default_invoice()
+            .with_amount(Decimal::ZERO)
+            .into_public_invoice("http://shop.example.com")
+            .build_event(InvoiceEventType::Created)
+            .into();
+
+        let result = sender
+            .build_request("http://webhook.example.com", event)
+            .unwrap();
+
+        assert_eq!(
+            result
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .unwrap(),
+            "application/vnd.example.webhook+json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_request_invalid_url() {
+        let dao = MockDaoInterface::default();
+        let hmac_config = HmacConfig::new(b"test".to_vec(), 10);
+
+        let sender = WebhookSender::new(
+            dao,
+            Some("not a valid url".to_string()),
+            hmac_config,
+            Duration::from_secs(60),
+            TEST_MAX_CONCURRENT_REQUESTS,
+            "application/json".to_string(),
+            TEST_MAX_ATTEMPTS,
+        );
+
+        let event: WebhookEvent = default_invoice()
+            .with_amount(Decimal::ZERO)
+            .into_public_invoice("http://shop.example.com")
+            .build_event(InvoiceEventType::Created)
+            .into();
+
+        assert!(
+            sender
+                .build_request("not a valid url", event)
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     #[tracing_test::traced_test]
     #[expect(clippy::cast_possible_truncation)]
@@ -496,6 +766,10 @@ mod tests {
             dao,
             Some("http://webhook.example.com".to_string()),
             hmac_config,
+            Duration::from_secs(60),
+            TEST_MAX_CONCURRENT_REQUESTS,
+            "application/json".to_string(),
+            TEST_MAX_ATTEMPTS,
         );
 
         // Test case 1:
@@ -508,16 +782,14 @@ mod tests {
         //   - Ids are equal to ids of returned events
         //   - (max - 2) futures returned
         {
-            let returned_events_count = WEBHOOK_SENDER_MAX_CONCURRENT_REQUESTS - 2;
+            let returned_events_count = TEST_MAX_CONCURRENT_REQUESTS - 2;
             let events = generate_events(returned_events_count);
             let events_ids: HashSet<_> = events.iter().map(|e| e.id).collect();
 
             sender
                 .dao
                 .expect_get_webhook_events_to_send()
-                .with(eq(
-                    WEBHOOK_SENDER_MAX_CONCURRENT_REQUESTS as u32,
-                ))
+                .with(eq(TEST_MAX_CONCURRENT_REQUESTS as u32))
                 .return_once(|_| Ok(events));
 
             let result = sender.prepare_webhook_events().await;
@@ -550,7 +822,7 @@ mod tests {
             assert_eq!(result.len(), returned_events_count);
             assert_eq!(
                 sender.processing_events_ids.len(),
-                WEBHOOK_SENDER_MAX_CONCURRENT_REQUESTS
+                TEST_MAX_CONCURRENT_REQUESTS
             );
             assert!(
                 sender
@@ -570,7 +842,7 @@ mod tests {
             assert!(result.is_empty());
             assert_eq!(
                 sender.processing_events_ids.len(),
-                WEBHOOK_SENDER_MAX_CONCURRENT_REQUESTS
+                TEST_MAX_CONCURRENT_REQUESTS
             );
         }
 
@@ -598,7 +870,7 @@ mod tests {
                 .dao
                 .expect_get_webhook_events_to_send()
                 .with(eq(
-                    (WEBHOOK_SENDER_MAX_CONCURRENT_REQUESTS - 1) as u32,
+                    (TEST_MAX_CONCURRENT_REQUESTS - 1) as u32
                 ))
                 .return_once(|_| Ok(events));
 
@@ -629,7 +901,7 @@ mod tests {
                 .dao
                 .expect_get_webhook_events_to_send()
                 .with(eq(
-                    (WEBHOOK_SENDER_MAX_CONCURRENT_REQUESTS - 2) as u32,
+                    (TEST_MAX_CONCURRENT_REQUESTS - 2) as u32
                 ))
                 .return_once(|_| Err(DaoWebhookEventError::DatabaseError));
 
@@ -653,26 +925,28 @@ mod tests {
             dao,
             Some("http://webhook.example.com".to_string()),
             hmac_config,
+            Duration::from_secs(60),
+            TEST_MAX_CONCURRENT_REQUESTS,
+            "application/json".to_string(),
+            TEST_MAX_ATTEMPTS,
         );
 
-        let mut events = generate_events(3);
-        let (event_1, event_2, event_3) = (
+        let mut events = generate_events(5);
+        let (event_1, event_2, event_3, event_4, event_5) = (
+            events.remove(4),
+            events.remove(3),
             events.remove(2),
             events.remove(1),
             events.remove(0),
         );
-        sender
-            .processing_events_ids
-            .insert(event_1.id);
-        sender
-            .processing_events_ids
-            .insert(event_2.id);
-        sender
-            .processing_events_ids
-            .insert(event_3.id);
+        for event in [&event_1, &event_2, &event_3, &event_4, &event_5] {
+            sender
+                .processing_events_ids
+                .insert(event.id);
+        }
 
         // Test case 1:
-        // - Webhook with ok result
+        // - Webhook with ok outcome
         // - Expectations:
         //   - Webhook id removed from internal queue
         //   - Single DAO call with respective webhook id
@@ -681,7 +955,9 @@ mod tests {
             let event_id = event_1.id;
             let webhook_result = SendWebhookResult {
                 event_id,
-                is_ok: true,
+                retry_meta: RetryMeta::default(),
+                outcome: SendWebhookOutcome::Ok,
+                failure_message: String::new(),
             };
 
             sender
@@ -694,7 +970,7 @@ mod tests {
                 .handle_send_webhook_result(webhook_result)
                 .await;
             sender.dao.checkpoint();
-            assert_eq!(sender.processing_events_ids.len(), 2);
+            assert_eq!(sender.processing_events_ids.len(), 4);
             assert!(
                 !sender
                     .processing_events_ids
@@ -703,22 +979,30 @@ mod tests {
         }
 
         // Test case 2:
-        // - Webhook with not ok result
+        // - Webhook with retriable outcome
         // - Expectations:
         //   - Webhook id removed from internal queue
-        //   - No DAO calls
+        //   - Single DAO call recording the failure with is_retriable = true
         {
             let event_id = event_2.id;
             let webhook_result = SendWebhookResult {
                 event_id,
-                is_ok: false,
+                retry_meta: RetryMeta::default(),
+                outcome: SendWebhookOutcome::Retriable,
+                failure_message: "HTTP 503 Service Unavailable".to_string(),
             };
 
+            sender
+                .dao
+                .expect_record_webhook_event_failure()
+                .withf(move |id, _, is_retriable| *id == event_id && *is_retriable)
+                .return_once(move |_, _, _| Ok(event_2));
+
             sender
                 .handle_send_webhook_result(webhook_result)
                 .await;
             sender.dao.checkpoint();
-            assert_eq!(sender.processing_events_ids.len(), 1);
+            assert_eq!(sender.processing_events_ids.len(), 3);
             assert!(
                 !sender
                     .processing_events_ids
@@ -727,7 +1011,39 @@ mod tests {
         }
 
         // Test case 3:
-        // - Error while mark webhook as sent
+        // - Webhook with permanent outcome
+        // - Expectations:
+        //   - Webhook id removed from internal queue
+        //   - Single DAO call recording the failure with is_retriable = false
+        {
+            let event_id = event_3.id;
+            let webhook_result = SendWebhookResult {
+                event_id,
+                retry_meta: RetryMeta::default(),
+                outcome: SendWebhookOutcome::Permanent,
+                failure_message: "HTTP 400 Bad Request".to_string(),
+            };
+
+            sender
+                .dao
+                .expect_record_webhook_event_failure()
+                .withf(move |id, _, is_retriable| *id == event_id && !*is_retriable)
+                .return_once(move |_, _, _| Ok(event_3));
+
+            sender
+                .handle_send_webhook_result(webhook_result)
+                .await;
+            sender.dao.checkpoint();
+            assert_eq!(sender.processing_events_ids.len(), 2);
+            assert!(
+                !sender
+                    .processing_events_ids
+                    .contains(&event_id)
+            );
+        }
+
+        // Test case 4:
+        // - Error while marking webhook as sent
         // - Expectations:
         //   - Webhook id removed from internal queue
         //   - Single DAO call
@@ -736,10 +1052,12 @@ mod tests {
             assert!(!logs_contain(
                 "Failed to mark webhook event as sent in database. It might be resent"
             ));
-            let event_id = event_3.id;
+            let event_id = event_4.id;
             let webhook_result = SendWebhookResult {
                 event_id,
-                is_ok: true,
+                retry_meta: RetryMeta::default(),
+                outcome: SendWebhookOutcome::Ok,
+                failure_message: String::new(),
             };
 
             sender
@@ -752,10 +1070,92 @@ mod tests {
                 .handle_send_webhook_result(webhook_result)
                 .await;
             sender.dao.checkpoint();
-            assert!(sender.processing_events_ids.is_empty());
+            assert_eq!(sender.processing_events_ids.len(), 1);
             assert!(logs_contain(
                 "Failed to mark webhook event as sent in database. It might be resent"
             ));
         }
+
+        // Test case 5:
+        // - Error while recording a delivery failure
+        // - Expectations:
+        //   - Webhook id removed from internal queue
+        //   - Single DAO call
+        //   - Error log recorded
+        {
+            assert!(!logs_contain(
+                "Failed to record webhook event delivery failure in database. It might be retried sooner than expected"
+            ));
+            let event_id = event_5.id;
+            let webhook_result = SendWebhookResult {
+                event_id,
+                retry_meta: RetryMeta::default(),
+                outcome: SendWebhookOutcome::Retriable,
+                failure_message: "connection refused".to_string(),
+            };
+
+            sender
+                .dao
+                .expect_record_webhook_event_failure()
+                .withf(move |id, _, is_retriable| *id == event_id && *is_retriable)
+                .return_once(move |_, _, _| Err(DaoWebhookEventError::DatabaseError));
+
+            sender
+                .handle_send_webhook_result(webhook_result)
+                .await;
+            sender.dao.checkpoint();
+            assert!(sender.processing_events_ids.is_empty());
+            assert!(logs_contain(
+                "Failed to record webhook event delivery failure in database. It might be retried sooner than expected"
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_send_webhook_result_dead_letters_retriable_failure_once_max_attempts_is_reached()
+     {
+        let dao = MockDaoInterface::default();
+        let hmac_config = HmacConfig::new(b"test".to_vec(), 10);
+
+        let mut sender = WebhookSender::new(
+            dao,
+            Some("http://webhook.example.com".to_string()),
+            hmac_config,
+            Duration::from_secs(60),
+            TEST_MAX_CONCURRENT_REQUESTS,
+            "application/json".to_string(),
+            TEST_MAX_ATTEMPTS,
+        );
+
+        let mut events = generate_events(1);
+        let event = events.remove(0);
+        let event_id = event.id;
+        sender
+            .processing_events_ids
+            .insert(event_id);
+
+        // This is the last retry before max_attempts is reached, so even
+        // though the failure is retriable, it has to be dead-lettered just
+        // like a non-retriable one rather than scheduled for another retry.
+        let webhook_result = SendWebhookResult {
+            event_id,
+            retry_meta: RetryMeta {
+                retry_count: TEST_MAX_ATTEMPTS - 1,
+                ..RetryMeta::default()
+            },
+            outcome: SendWebhookOutcome::Retriable,
+            failure_message: "connection refused".to_string(),
+        };
+
+        sender
+            .dao
+            .expect_record_webhook_event_failure()
+            .withf(move |id, _, is_retriable| *id == event_id && !*is_retriable)
+            .return_once(move |_, _, _| Ok(event));
+
+        sender
+            .handle_send_webhook_result(webhook_result)
+            .await;
+        sender.dao.checkpoint();
     }
 }